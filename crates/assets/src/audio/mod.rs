@@ -0,0 +1,20 @@
+use bevy::prelude::*;
+use bevy_asset_loader::prelude::*;
+use bevy_kira_audio::AudioSource;
+
+/// Asset collection for the music and sound effects in `assets/audio`.
+#[derive(AssetCollection, Resource, Debug)]
+pub struct AudioAssets {
+    #[asset(key = "audio.music.battle")]
+    pub battle_music: Handle<AudioSource>,
+    #[asset(key = "audio.music.game_over")]
+    pub game_over_music: Handle<AudioSource>,
+    #[asset(key = "audio.sfx.hit")]
+    pub hit_sfx: Handle<AudioSource>,
+    #[asset(key = "audio.sfx.dash")]
+    pub dash_sfx: Handle<AudioSource>,
+    #[asset(key = "audio.sfx.level_up")]
+    pub level_up_sfx: Handle<AudioSource>,
+    #[asset(key = "audio.sfx.death")]
+    pub death_sfx: Handle<AudioSource>,
+}
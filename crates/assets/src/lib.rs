@@ -1,4 +1,5 @@
+pub mod audio;
 pub mod player;
 pub mod spawnable;
+pub mod ui;
 pub mod weapon;
-
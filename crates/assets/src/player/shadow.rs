@@ -1,5 +1,6 @@
 use bevy::prelude::*;
 use bevy_asset_loader::prelude::*;
+use engine::character::CharacterType;
 
 // -----------------------------------------------------------------------------
 // Shadow Player Animation Assets: Combined
@@ -27,11 +28,77 @@ pub struct PlayerShadowAssets {
     pub run_image: Handle<Image>,
 
     // --- Light Attack Animation ---
-    /// The texture atlas layout used for the run animation (8 frames, 128x128 each).
+    /// The texture atlas layout used for the first light attack combo step (6 frames, 128x128 each).
     #[asset(key = "shadow.light.attack.layout")]
     pub light_attack_layout: Handle<TextureAtlasLayout>,
 
-    /// The image for the shadow player's run animation.
+    /// The image for the shadow player's first light attack combo step.
     #[asset(key = "shadow.light.attack.image")]
     pub light_attack_image: Handle<Image>,
+
+    /// The texture atlas layout used for the second light attack combo step (3 frames, 128x128 each).
+    #[asset(key = "shadow.light.attack.2.layout")]
+    pub light_attack_2_layout: Handle<TextureAtlasLayout>,
+
+    /// The image for the shadow player's second light attack combo step.
+    #[asset(key = "shadow.light.attack.2.image")]
+    pub light_attack_2_image: Handle<Image>,
+
+    /// The texture atlas layout used for the third light attack combo step (3 frames, 128x128 each).
+    #[asset(key = "shadow.light.attack.3.layout")]
+    pub light_attack_3_layout: Handle<TextureAtlasLayout>,
+
+    /// The image for the shadow player's third light attack combo step.
+    #[asset(key = "shadow.light.attack.3.image")]
+    pub light_attack_3_image: Handle<Image>,
+
+    /// The texture atlas layout used for the fourth light attack combo step (10 frames, 128x128 each).
+    #[asset(key = "shadow.light.attack.4.layout")]
+    pub light_attack_4_layout: Handle<TextureAtlasLayout>,
+
+    /// The image for the shadow player's fourth light attack combo step.
+    #[asset(key = "shadow.light.attack.4.image")]
+    pub light_attack_4_image: Handle<Image>,
+
+    // --- Heavy Attack Animation ---
+    /// The texture atlas layout used for the heavy attack animation (10 frames, 128x128 each).
+    /// Also used while charging.
+    #[asset(key = "shadow.heavy.attack.layout")]
+    pub heavy_attack_layout: Handle<TextureAtlasLayout>,
+
+    /// The image for the shadow player's heavy attack animation.
+    #[asset(key = "shadow.heavy.attack.image")]
+    pub heavy_attack_image: Handle<Image>,
+}
+
+impl PlayerShadowAssets {
+    /// Characters drawn with the shadow player assets.
+    pub const CHARACTERS: &'static [CharacterType] =
+        &[CharacterType::ShadowMonarch];
+
+    /// Returns the image and layout of the given light attack combo step.
+    /// Steps past the last sheet reuse the last one.
+    pub fn light_attack_clip(
+        &self,
+        step: usize,
+    ) -> (Handle<Image>, Handle<TextureAtlasLayout>) {
+        match step {
+            0 => (
+                self.light_attack_image.clone(),
+                self.light_attack_layout.clone(),
+            ),
+            1 => (
+                self.light_attack_2_image.clone(),
+                self.light_attack_2_layout.clone(),
+            ),
+            2 => (
+                self.light_attack_3_image.clone(),
+                self.light_attack_3_layout.clone(),
+            ),
+            _ => (
+                self.light_attack_4_image.clone(),
+                self.light_attack_4_layout.clone(),
+            ),
+        }
+    }
 }
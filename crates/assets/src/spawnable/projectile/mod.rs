@@ -11,6 +11,10 @@ pub struct ProjectileAssets {
     pub ally_bullet_layout: Handle<TextureAtlasLayout>,
     #[asset(key = "ally_bullet.image")]
     pub ally_bullet_image: Handle<Image>,
+    #[asset(key = "enemy_bullet.layout")]
+    pub enemy_bullet_layout: Handle<TextureAtlasLayout>,
+    #[asset(key = "enemy_bullet.image")]
+    pub enemy_bullet_image: Handle<Image>,
 }
 
 impl ProjectileAssets {
@@ -22,6 +26,7 @@ impl ProjectileAssets {
         match projectile_type {
             ProjectileType::Bullet(faction) => match faction {
                 Faction::Ally => self.ally_bullet_layout.clone(),
+                Faction::Enemy => self.enemy_bullet_layout.clone(),
             },
         }
     }
@@ -31,6 +36,7 @@ impl ProjectileAssets {
         match projectile_type {
             ProjectileType::Bullet(faction) => match faction {
                 Faction::Ally => self.ally_bullet_image.clone(),
+                Faction::Enemy => self.enemy_bullet_image.clone(),
             },
         }
     }
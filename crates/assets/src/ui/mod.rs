@@ -0,0 +1,33 @@
+use bevy::prelude::*;
+use bevy_asset_loader::prelude::*;
+use serde::Deserialize;
+
+/// Font of a UI text, looked up in [`UiAssets`].
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum UiFont {
+    Regular,
+    Display,
+    Title,
+}
+
+/// Asset collection for the fonts shipped in `assets/ui`.
+#[derive(AssetCollection, Resource, Debug)]
+pub struct UiAssets {
+    #[asset(key = "ui.font.regular")]
+    pub regular_font: Handle<Font>,
+    #[asset(key = "ui.font.display")]
+    pub display_font: Handle<Font>,
+    #[asset(key = "ui.font.title")]
+    pub title_font: Handle<Font>,
+}
+
+impl UiAssets {
+    /// Use a UiFont enum to access a font handle
+    pub fn get_font(&self, font: UiFont) -> Handle<Font> {
+        match font {
+            UiFont::Regular => self.regular_font.clone(),
+            UiFont::Display => self.display_font.clone(),
+            UiFont::Title => self.title_font.clone(),
+        }
+    }
+}
@@ -2,6 +2,7 @@ use bevy::prelude::*;
 use bevy::asset::Handle;
 use bevy::image::Image;
 use bevy_asset_loader::prelude::*;
+use engine::weapon::WeaponSprite;
 
 /// Asset collection for all weapons.
 #[derive(AssetCollection, Resource, Debug)]
@@ -10,3 +11,12 @@ pub struct WeaponAssets {
     #[asset(key = "weapon.staff.image")]
     pub staff_image: Handle<Image>,
 }
+
+impl WeaponAssets {
+    /// Use a WeaponSprite enum to access an image handle
+    pub fn get_image(&self, sprite: &WeaponSprite) -> Handle<Image> {
+        match sprite {
+            WeaponSprite::IceStaff => self.staff_image.clone(),
+        }
+    }
+}
@@ -1,12 +1,13 @@
 use std::collections::HashMap;
+use std::fmt;
+use std::time::Duration;
 
 use bevy::prelude::*;
-use serde::{Deserialize, Serialize};
-
-use crate::abilities::shadow_monarch::{
-    MonarchFormData, ShadowDashData, ShadowSummonData,
-};
-use crate::player::PlayerIDComponent;
+use ron::error::SpannedError;
+use ron::value::RawValue;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use thiserror::Error;
 
 pub mod shadow_monarch;
 
@@ -17,37 +18,59 @@ pub enum AbilitySlotIDComponent {
     Three,
 }
 
-/// Abilities that can occupy the first skill slot of a character.
-#[derive(Clone, Deserialize, PartialEq, Eq, Hash)]
-pub enum SlotOneAbilityType {
-    /// Sung Jin-Woo's shadow resurrection ability.
-    ShadowSummon,
+impl AbilitySlotIDComponent {
+    /// Maps the 1-based slot number carried by `UseSkillEvent` to a slot id.
+    pub fn from_index(index: usize) -> Option<Self> {
+        match index {
+            1 => Some(Self::One),
+            2 => Some(Self::Two),
+            3 => Some(Self::Three),
+            _ => None,
+        }
+    }
 }
 
-/// Abilities that can occupy the second skill slot of a character.
-#[derive(Clone, Deserialize, PartialEq, Eq, Hash)]
-pub enum SlotTwoAbilityType {
-    /// Sung Jin-Woo's shadow dash ability.
-    ShadowDash,
+/// Unique identifier of an ability (e.g. `"ShadowSummon"`).
+/// Characters reference abilities by this id in `characters.ron` and
+/// `abilities.ron` is keyed by it.
+#[derive(Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
+#[serde(transparent)]
+pub struct AbilityId(pub String);
+
+impl AbilityId {
+    pub fn new(id: impl Into<String>) -> Self {
+        Self(id.into())
+    }
 }
 
-/// Ultimate or transformation abilities in slot 3.
-#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq, Hash)]
-pub enum SlotThreeAbilityType {
-    /// Sung Jin-Woo's power-up form
-    MonarchForm,
+impl fmt::Display for AbilityId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Implemented by the runtime component of every ability so that it can be
+/// added to the [`AbilityRegistry`] once and then placed in any slot.
+pub trait Ability: Component + for<'a> From<&'a Self::Data> {
+    /// Id used for this ability in the data files.
+    const ID: &'static str;
+
+    /// Deserializable configuration found under `ability` in `abilities.ron`.
+    type Data: DeserializeOwned + Send + Sync + 'static;
+
+    fn id() -> AbilityId {
+        AbilityId::new(Self::ID)
+    }
 }
 
-/// Hashmaps of ability types to descriptions
+/// Hashmap of ability ids to descriptions
 /// Used for providing information to user on character selection screen
 #[derive(Resource, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct AbilityDescriptionsResource {
-    pub slot_one: HashMap<SlotOneAbilityType, String>,
-    pub slot_two: HashMap<SlotTwoAbilityType, String>,
-    pub slot_three: HashMap<SlotThreeAbilityType, String>,
+    pub descriptions: HashMap<AbilityId, String>,
 }
 
-
 /// Component for tracking ability cooldowns
 #[derive(Component, Deserialize, Clone)]
 pub struct AbilityCooldownComponent {
@@ -59,27 +82,285 @@ pub struct AbilityCooldownComponent {
 }
 
 impl AbilityCooldownComponent {
+    /// Creates a cooldown that is already elapsed, so the ability can be used right away.
     pub fn new(cooldown_time: f32) -> Self {
+        let mut cooldown_timer =
+            Timer::from_seconds(cooldown_time, TimerMode::Once);
+        cooldown_timer.tick(cooldown_timer.duration());
+
         Self {
             cooldown_time,
-            cooldown_timer: Timer::from_seconds(cooldown_time, TimerMode::Once),
+            cooldown_timer,
         }
     }
+
+    /// Returns `true` if the ability can be activated.
+    pub fn is_ready(&self) -> bool {
+        self.cooldown_timer.finished()
+    }
+
+    /// Restarts the cooldown, called when the ability is activated.
+    pub fn start(&mut self) {
+        self.cooldown_timer.reset();
+    }
+
+    /// Changes the cooldown duration without restarting it.
+    /// Time already elapsed on an in-progress cooldown carries over.
+    pub fn set_cooldown_time(&mut self, cooldown_time: f32) {
+        self.cooldown_time = cooldown_time;
+        self.cooldown_timer
+            .set_duration(Duration::from_secs_f32(cooldown_time));
+    }
 }
 
-/// Stores the attributes for all abilities in the game.
-/// Each ability is tied to its data type and can be used to generate ECS components.
+/// Stores the attributes for all abilities in the game, keyed by ability id.
+/// The ability specific part is kept as raw RON until the registered loader
+/// turns it into the ability's data type.
 #[derive(Resource, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct AbilitiesResource {
-    // === Slot1Abilities ===
-    /// Sung Jin-Woo's resurrection ability.
-    pub shadow_summon: ShadowSummonData,
+    pub abilities: HashMap<AbilityId, AbilityData>,
+}
+
+/// Rank table of an ability. The first entry is rank 1.
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct AbilityData {
+    pub ranks: Vec<AbilityRankData>,
+}
+
+impl AbilityData {
+    /// Returns the data of a 1-based rank.
+    pub fn rank(&self, rank: u32) -> Option<&AbilityRankData> {
+        let index = usize::try_from(rank).ok()?.checked_sub(1)?;
+        self.ranks.get(index)
+    }
+
+    /// Highest rank the ability can reach.
+    pub fn max_rank(&self) -> u32 {
+        self.ranks.len() as u32
+    }
+}
 
-    // === Slot2Abilities ===
-    /// Sung Jin-Woo’s shadow dash.
-    pub shadow_dash: ShadowDashData,
+/// Attributes shared by all abilities plus the ability specific configuration
+/// for a single rank.
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct AbilityRankData {
+    pub cooldown_time: f32,
+    /// Mana spent when the ability is cast. Free if omitted.
+    #[serde(default)]
+    pub mana_cost: u32,
+    pub ability: Box<RawValue>,
+}
+
+impl AbilityRankData {
+    /// Deserializes the ability specific configuration into `A::Data`.
+    pub fn load<A: Ability>(&self) -> Result<A::Data, AbilityError> {
+        self.ability
+            .into_rust::<A::Data>()
+            .map_err(|source| {
+                AbilityError::InvalidData {
+                    id: A::id(),
+                    source,
+                }
+            })
+    }
+}
+
+/// Identifies which registered ability an ability entity is.
+#[derive(Component, Clone, Debug, PartialEq)]
+pub struct AbilityIdComponent(pub AbilityId);
+
+/// Mana an ability costs to cast at its current rank.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Default)]
+pub struct AbilityManaCostComponent(pub u32);
+
+/// Current rank of an ability entity (1-based).
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct AbilityRankComponent {
+    /// Current rank, starting at 1.
+    pub rank: u32,
+    /// Highest rank defined in the ability's rank table.
+    pub max_rank: u32,
+}
 
-    // === Slot3Abilities ===
-    /// Sung Jin-Woo's ultimate transformation.
-    pub monarch_form: MonarchFormData,
+impl AbilityRankComponent {
+    /// Returns `true` if the ability cannot be ranked up any further.
+    pub fn is_max(&self) -> bool {
+        self.rank >= self.max_rank
+    }
+}
+
+/// Components shared by every ability entity. The ability's own component is
+/// inserted separately from the data of its current rank.
+#[derive(Bundle, Clone)]
+pub struct AbilityBundle {
+    /// Which registered ability this entity is
+    pub id: AbilityIdComponent,
+    /// Slot identifier (e.g., Slot1, Slot2) used for ability mapping
+    pub slot: AbilitySlotIDComponent,
+    /// Cooldown tracker for the ability
+    pub cooldown: AbilityCooldownComponent,
+    /// Mana spent on every cast
+    pub mana_cost: AbilityManaCostComponent,
+    /// Current rank of the ability
+    pub rank: AbilityRankComponent,
+}
+
+/// Errors raised while resolving an ability id into an ability entity.
+#[derive(Error, Debug)]
+pub enum AbilityError {
+    #[error("ability `{0}` is not registered")]
+    Unregistered(AbilityId),
+    #[error("ability `{0}` has no entry in abilities.ron")]
+    MissingData(AbilityId),
+    #[error("ability `{id}` has no rank {rank}")]
+    MissingRank { id: AbilityId, rank: u32 },
+    #[error("ability `{id}` has invalid data: {source}")]
+    InvalidData {
+        id: AbilityId,
+        source: SpannedError,
+    },
+}
+
+/// Type erased functions of a registered ability.
+#[derive(Clone, Copy)]
+pub struct RegisteredAbility {
+    /// Parses the ability specific data of a rank to check that it is well-formed.
+    pub load: fn(&AbilityRankData) -> Result<(), AbilityError>,
+    /// Inserts (or replaces) the ability's component built from a rank's data.
+    pub apply_rank:
+        fn(&mut EntityCommands, &AbilityRankData) -> Result<(), AbilityError>,
+}
+
+impl RegisteredAbility {
+    fn of<A: Ability>() -> Self {
+        Self {
+            load: |data| data.load::<A>().map(|_| ()),
+            apply_rank: |entity_commands, data| {
+                let ability_data = data.load::<A>()?;
+                entity_commands.insert(A::from(&ability_data));
+                Ok(())
+            },
+        }
+    }
+}
+
+/// Every ability known to the game, keyed by ability id.
+/// Abilities are added once through `register`, after which any character
+/// can reference them from any slot.
+#[derive(Resource, Default)]
+pub struct AbilityRegistry {
+    abilities: HashMap<AbilityId, RegisteredAbility>,
+}
+
+impl AbilityRegistry {
+    /// Adds the ability `A` to the registry.
+    pub fn register<A: Ability>(&mut self) {
+        self.abilities.insert(A::id(), RegisteredAbility::of::<A>());
+    }
+
+    /// Returns the registered functions of an ability.
+    pub fn get(&self, id: &AbilityId) -> Option<&RegisteredAbility> {
+        self.abilities.get(id)
+    }
+
+    /// Returns `true` if an ability with the given id was registered.
+    pub fn contains(&self, id: &AbilityId) -> bool {
+        self.abilities.contains_key(id)
+    }
+
+    fn lookup<'a>(
+        &self,
+        abilities_res: &'a AbilitiesResource,
+        id: &AbilityId,
+    ) -> Result<(RegisteredAbility, &'a AbilityData), AbilityError> {
+        let registered = self
+            .get(id)
+            .ok_or_else(|| AbilityError::Unregistered(id.clone()))?;
+        let data = abilities_res
+            .abilities
+            .get(id)
+            .ok_or_else(|| AbilityError::MissingData(id.clone()))?;
+
+        Ok((*registered, data))
+    }
+
+    /// Spawns the ability with the given id at rank 1 as a child entity
+    /// occupying `slot`.
+    pub fn spawn_ability(
+        &self,
+        parent: &mut ChildBuilder,
+        abilities_res: &AbilitiesResource,
+        id: &AbilityId,
+        slot: AbilitySlotIDComponent,
+    ) -> Result<Entity, AbilityError> {
+        let (registered, data) = self.lookup(abilities_res, id)?;
+        let rank_data = data.rank(1).ok_or_else(|| {
+            AbilityError::MissingRank {
+                id: id.clone(),
+                rank: 1,
+            }
+        })?;
+
+        let mut entity_commands = parent.spawn((
+            Name::new(id.to_string()),
+            AbilityBundle {
+                id: AbilityIdComponent(id.clone()),
+                slot,
+                cooldown: AbilityCooldownComponent::new(
+                    rank_data.cooldown_time,
+                ),
+                mana_cost: AbilityManaCostComponent(rank_data.mana_cost),
+                rank: AbilityRankComponent {
+                    rank: 1,
+                    max_rank: data.max_rank(),
+                },
+            },
+        ));
+        if let Err(error) =
+            (registered.apply_rank)(&mut entity_commands, rank_data)
+        {
+            entity_commands.despawn();
+            return Err(error);
+        }
+
+        Ok(entity_commands.id())
+    }
+
+    /// Raises an ability entity by one rank.
+    ///
+    /// The ability's component is rebuilt from the new rank's data and the
+    /// cooldown duration and mana cost are updated, but time already elapsed
+    /// on an in-progress cooldown is kept. Returns `Ok(false)` if the ability
+    /// is already at its maximum rank.
+    pub fn rank_up(
+        &self,
+        entity_commands: &mut EntityCommands,
+        abilities_res: &AbilitiesResource,
+        id: &AbilityId,
+        rank: &mut AbilityRankComponent,
+        cooldown: &mut AbilityCooldownComponent,
+    ) -> Result<bool, AbilityError> {
+        if rank.is_max() {
+            return Ok(false);
+        }
+
+        let (registered, data) = self.lookup(abilities_res, id)?;
+        let next_rank = rank.rank + 1;
+        let rank_data = data.rank(next_rank).ok_or_else(|| {
+            AbilityError::MissingRank {
+                id: id.clone(),
+                rank: next_rank,
+            }
+        })?;
+
+        (registered.apply_rank)(entity_commands, rank_data)?;
+        cooldown.set_cooldown_time(rank_data.cooldown_time);
+        entity_commands.insert(AbilityManaCostComponent(rank_data.mana_cost));
+        rank.rank = next_rank;
+
+        Ok(true)
+    }
 }
@@ -1,42 +1,11 @@
 use bevy::prelude::*;
 use serde::Deserialize;
 
-use crate::abilities::{AbilityCooldownComponent, AbilitySlotIDComponent};
-
-// === Ability1 ===
-/// Bundle used for spawning the shadow summon ability entity as part of a player or systems.
-/// Groups all related components together for simplified spawning.
-#[derive(Bundle, Clone)]
-pub struct ShadowSummonBundle {
-    /// Slot identifier (e.g., Slot1, Slot2) used for ability mapping
-    pub slot: AbilitySlotIDComponent,
-
-    /// Cooldown tracker for the ability
-    pub cooldown: AbilityCooldownComponent,
-
-    /// Core logic and configuration for the shadow summon behavior
-    pub ability: ShadowSummonComponent,
-}
-
-impl From<&ShadowSummonData> for ShadowSummonBundle {
-    fn from(data: &ShadowSummonData) -> Self {
-        Self {
-            slot: data.slot,
-            cooldown: AbilityCooldownComponent::new(data.cooldown_time),
-            ability: ShadowSummonComponent::from(data.ability),
-        }
-    }
-}
-
-/// Deserializable data for initializing a `ShadowSummonBundle`.
-/// Used during asset loading or runtime configuration.
-#[derive(Deserialize, Clone, Copy, Debug)]
-pub struct ShadowSummonData {
-    pub slot: AbilitySlotIDComponent,
-    pub cooldown_time: f32,
-    pub ability: ShadowSummonComponentData,
-}
+use crate::abilities::Ability;
+use crate::spawnable::mob::shadow::ShadowTargetPolicy;
+use crate::status_effect::StatusEffectId;
 
+// === ShadowSummon ===
 /// Component responsible for tracking summon-related gameplay logic.
 /// This component handles stats and timers of active summons derived from defeated enemies.
 #[derive(Component, Clone, Debug)]
@@ -55,10 +24,28 @@ pub struct ShadowSummonComponent {
 
     /// Optional timer tracking summon lifetime. If None, summon is permanent.
     pub lifetime: Option<Timer>,
+
+    /// Movement speed of summons (units per second)
+    pub move_speed: f32,
+
+    /// Distance at which summons can hit their target
+    pub attack_range: f32,
+
+    /// Seconds between two hits of a summon
+    pub attack_interval: f32,
+
+    /// How summons pick their targets
+    pub target_policy: ShadowTargetPolicy,
+
+    /// Status effect applied by every hit of a summon
+    pub on_hit_status_effect: Option<StatusEffectId>,
+
+    /// Knockback force of every hit of a summon
+    pub knockback: f32,
 }
 
-impl From<ShadowSummonComponentData> for ShadowSummonComponent {
-    fn from(data: ShadowSummonComponentData) -> Self {
+impl From<&ShadowSummonComponentData> for ShadowSummonComponent {
+    fn from(data: &ShadowSummonComponentData) -> Self {
         Self {
             max_summons: data.max_summons,
             max_radius: data.max_radius,
@@ -67,53 +54,43 @@ impl From<ShadowSummonComponentData> for ShadowSummonComponent {
             lifetime: data
                 .duration
                 .map(|secs| Timer::from_seconds(secs, TimerMode::Once)),
+            move_speed: data.move_speed,
+            attack_range: data.attack_range,
+            attack_interval: data.attack_interval,
+            target_policy: data.target_policy,
+            on_hit_status_effect: data.on_hit_status_effect.clone(),
+            knockback: data.knockback,
         }
     }
 }
 
+impl Ability for ShadowSummonComponent {
+    const ID: &'static str = "ShadowSummon";
+    type Data = ShadowSummonComponentData;
+}
+
 /// Deserializable data for configuring a `ShadowSummonComponent`.
 /// Defines logic for resurrecting defeated enemies into summons.
-#[derive(Deserialize, Clone, Copy, Debug)]
+#[derive(Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
 pub struct ShadowSummonComponentData {
     pub max_summons: u32,
     pub max_radius: f32,
     pub health_percentage: f32,
     pub damage_percentage: f32,
     pub duration: Option<f32>,
-}
-
-// === Ability2 ===
-/// Bundle used to spawn and configure a dash ability entity.
-/// Includes metadata like cooldown and slot assignment.
-#[derive(Bundle, Clone)]
-pub struct ShadowDashBundle {
-    /// Which ability slot this ability occupies (e.g., Slot1, Slot2).
-    pub slot: AbilitySlotIDComponent,
-    /// Cooldown component storing internal timer state.
-    pub cooldown: AbilityCooldownComponent,
-    /// Actual dash behavior and parameters.
-    pub ability: ShadowDashComponent,
-}
-
-impl From<&ShadowDashData> for ShadowDashBundle {
-    fn from(data: &ShadowDashData) -> Self {
-        Self {
-            slot: data.slot,
-            cooldown: AbilityCooldownComponent::new(data.cooldown_time),
-            ability: ShadowDashComponent::from(&data.ability),
-        }
-    }
-}
-
-/// Deserializable configuration data for a full dash ability.
-/// This includes slot placement, cooldown, and behavior.
-#[derive(Deserialize, Clone, Debug)]
-pub struct ShadowDashData {
-    pub slot: AbilitySlotIDComponent,
-    pub cooldown_time: f32,
-    pub ability: ShadowDashComponentData,
-}
-
+    pub move_speed: f32,
+    pub attack_range: f32,
+    pub attack_interval: f32,
+    #[serde(default)]
+    pub target_policy: ShadowTargetPolicy,
+    #[serde(default)]
+    pub on_hit_status_effect: Option<StatusEffectId>,
+    #[serde(default)]
+    pub knockback: f32,
+}
+
+// === ShadowDash ===
 /// Component representing the active dash state of a shadow entity.
 /// Applies a temporary movement boost in a specified direction.
 #[derive(Component, Clone)]
@@ -123,6 +100,7 @@ pub struct ShadowDashComponent {
     /// How much faster the entity moves during the dash (e.g., 2.0 = 2x speed).
     pub speed_multiplier: f32,
     /// Direction of the dash as a normalized 2D vector.
+    /// A zero vector dashes in the direction the player is moving.
     pub direction: Vec2,
 }
 
@@ -136,47 +114,33 @@ impl From<&ShadowDashComponentData> for ShadowDashComponent {
     }
 }
 
+impl Ability for ShadowDashComponent {
+    const ID: &'static str = "ShadowDash";
+    type Data = ShadowDashComponentData;
+}
+
 /// Deserializable configuration data for a `ShadowDashComponent`.
 /// Typically loaded from external files (e.g., `.ron` or `.json`).
 #[derive(Deserialize, Clone, Copy, Debug)]
+#[serde(deny_unknown_fields)]
 pub struct ShadowDashComponentData {
     pub duration: f32,
     pub speed_multiplier: f32,
     pub direction: (f32, f32),
 }
 
-// === Ability3 ===
-/// Bundle used to spawn a `MonarchForm` ability entity.
-/// Combines slot, cooldown tracking, and the core behavior component.
-#[derive(Bundle, Clone)]
-pub struct MonarchFormBundle {
-    /// Which ability slot this form occupies.
-    pub slot: AbilitySlotIDComponent,
-    /// Cooldown timer component for reuse delay.
-    pub cooldown: AbilityCooldownComponent,
-    /// Actual Monarch Form behavior.
-    pub ability: MonarchFormComponent,
-}
-
-impl From<&MonarchFormData> for MonarchFormBundle {
-    fn from(data: &MonarchFormData) -> Self {
-        Self {
-            slot: data.slot,
-            cooldown: AbilityCooldownComponent::new(data.cooldown_time),
-            ability: MonarchFormComponent::from(&data.ability),
-        }
-    }
-}
-
-/// Deserializable configuration data for a complete Monarch Form ability.
-/// This includes slot placement, cooldown duration, and form behavior.
-#[derive(Deserialize, Clone, Debug)]
-pub struct MonarchFormData {
-    pub slot: AbilitySlotIDComponent,
-    pub cooldown_time: f32,
-    pub ability: MonarchFormComponentData,
+/// Added to a player while a shadow dash is in progress.
+#[derive(Component, Clone, Debug)]
+pub struct ShadowDashingComponent {
+    /// Normalized dash direction.
+    pub direction: Vec2,
+    /// Speed (units per second) the player moves at while dashing.
+    pub speed: f32,
+    /// Remaining duration of the dash.
+    pub timer: Timer,
 }
 
+// === MonarchForm ===
 /// Runtime component representing the Monarch Form state.
 /// Adds temporary bonuses to health and damage for a limited time.
 #[derive(Component, Clone, Copy, Debug)]
@@ -199,11 +163,29 @@ impl From<&MonarchFormComponentData> for MonarchFormComponent {
     }
 }
 
+impl Ability for MonarchFormComponent {
+    const ID: &'static str = "MonarchForm";
+    type Data = MonarchFormComponentData;
+}
+
 /// Deserializable configuration data for a `MonarchFormComponent`.
 /// Typically loaded from external files (e.g., `.ron`, `.json`).
 #[derive(Deserialize, Clone, Copy, Debug)]
+#[serde(deny_unknown_fields)]
 pub struct MonarchFormComponentData {
     pub health_percentage: f32,
     pub damage_percentage: f32,
     pub duration: f32,
 }
+
+/// Added to a player while the Monarch Form is active.
+/// Stores the applied bonuses so they can be reverted when the form ends.
+#[derive(Component, Clone, Debug)]
+pub struct MonarchFormActiveComponent {
+    /// Bonus max health granted by the form.
+    pub bonus_health: u32,
+    /// Bonus weapon damage granted by the form.
+    pub bonus_damage: u32,
+    /// Remaining duration of the form.
+    pub timer: Timer,
+}
@@ -0,0 +1,64 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// How players aim their weapons, projectiles and directional abilities.
+#[derive(
+    Deserialize,
+    Serialize,
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Hash
+)]
+pub enum AimMode {
+    /// Towards the mouse cursor.
+    #[default]
+    Mouse,
+    /// Along the right stick of the first gamepad.
+    RightStick,
+    /// Towards the nearest living enemy.
+    AutoNearest,
+}
+
+/// Direction a player aims at, updated every frame according to the
+/// [`AimMode`] of the settings. Everything a player aims reads it instead
+/// of the raw input.
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct AimComponent {
+    /// Normalized aimed direction, in world space.
+    pub direction: Vec2,
+}
+
+impl Default for AimComponent {
+    fn default() -> Self {
+        Self { direction: Vec2::X }
+    }
+}
+
+impl AimComponent {
+    /// Aims towards `direction`. A zero direction keeps the current one.
+    pub fn set(&mut self, direction: Vec2) {
+        if let Some(direction) = direction.try_normalize() {
+            self.direction = direction;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zero_direction_keeps_aim() {
+        let mut aim = AimComponent::default();
+
+        aim.set(Vec2::new(0.0, -3.0));
+        assert_eq!(aim.direction, Vec2::NEG_Y);
+
+        aim.set(Vec2::ZERO);
+        assert_eq!(aim.direction, Vec2::NEG_Y);
+    }
+}
@@ -28,8 +28,30 @@ impl From<&AnimationData> for AnimationComponent {
 }
 
 #[derive(Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
 pub struct AnimationData {
     pub frame_duration: f32,
     pub mode: AnimationTimerMode,
     pub direction: AnimationDirection,
+    /// Tagged frames, reported with an `AnimationFrameEvent` when entered
+    #[serde(default)]
+    pub events: Vec<AnimationFrameTag>,
+}
+
+impl AnimationData {
+    /// Returns the tags of the given frame
+    pub fn tags_at(&self, frame: usize) -> impl Iterator<Item = &str> {
+        self.events
+            .iter()
+            .filter(move |event| event.frame == frame)
+            .map(|event| event.tag.as_str())
+    }
+}
+
+/// A tag on a frame of a clip, e.g. `(frame: 2, tag: "footstep")`
+#[derive(Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct AnimationFrameTag {
+    pub frame: usize,
+    pub tag: String,
 }
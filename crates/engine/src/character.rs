@@ -1,7 +1,9 @@
-use super::abilities::{
-    SlotOneAbilityType, SlotThreeAbilityType, SlotTwoAbilityType,
-};
+use super::abilities::AbilityId;
+use crate::combat::{DamageVariance, ResistancesComponent};
+use crate::melee::{HeavyAttackData, LightAttackData};
+use crate::spawnable::projectile::behavior::ProjectileId;
 use crate::spawnable::SpawnPosition;
+use crate::weapon::WeaponId;
 use bevy::prelude::*;
 use serde::Deserialize;
 use strum_macros::EnumIter;
@@ -53,6 +55,7 @@ pub enum CharacterStatType {
 ///
 /// Inspired by games like *Vampire Survivors* and the *Solo Leveling* universe.
 #[derive(Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
 pub struct Character {
 	// === Meta ===
 	/// Display name of the character (for UI and selection screen).
@@ -83,15 +86,49 @@ pub struct Character {
 	/// Number of projectiles emitted per attack cycle.
 	pub projectile_count: u32,
 
+	/// Kind of projectile fired, defined in `projectiles.ron`.
+	pub projectile: ProjectileId,
+
+	/// Chance (0.0 to 1.0) that a hit is a critical strike.
+	pub crit_chance: f32,
+
+	/// Damage multiplier of critical strikes.
+	pub crit_multiplier: f32,
+
+	/// Optional random range every hit's damage is multiplied by.
+	#[serde(default)]
+	pub damage_variance: Option<DamageVariance>,
+
+	/// Weapon the character holds at the start of a run, defined in
+	/// `weapons.ron`.
+	pub weapon: WeaponId,
+
+	/// Auto-firing weapons the character starts a run with, defined in
+	/// `weapons.ron`.
+	#[serde(default)]
+	pub starting_weapons: Vec<WeaponId>,
+
+	/// Combo of melee swings performed by the light attack.
+	pub light_attack: LightAttackData,
+
+	/// Charged swing performed by the heavy attack.
+	pub heavy_attack: HeavyAttackData,
+
 	// === Abilities ===
 	/// Assigned ability for the first active skill slot (if any).
-	pub slot_1_ability: Option<SlotOneAbilityType>,
+	pub slot_1_ability: Option<AbilityId>,
 
 	/// Assigned ability for the second active skill slot (if any).
-	pub slot_2_ability: Option<SlotTwoAbilityType>,
+	pub slot_2_ability: Option<AbilityId>,
 
 	/// Assigned ability for the third active skill slot (if any).
-	pub slot_3_ability: Option<SlotThreeAbilityType>,
+	pub slot_3_ability: Option<AbilityId>,
+
+	/// Total mana points available for casting abilities.
+	pub mana: u32,
+
+	/// Mana regenerated every regeneration tick.
+	pub mana_regen: u32,
 
 	// === Defense ===
 	/// Total health points for the character.
@@ -101,6 +138,14 @@ pub struct Character {
 	/// For example, `0.1` = 10% lifesteal.
 	pub life_steal_percent: f32,
 
+	/// Fraction of knockback and hit-stun the character ignores.
+	#[serde(default)]
+	pub knockback_resistance: f32,
+
+	/// Resistance per damage type. Missing damage types are taken in full.
+	#[serde(default)]
+	pub resistances: ResistancesComponent,
+
 	// === Mobility ===
 	/// Acceleration applied when movement input is given.
 	pub acceleration: Vec2,
@@ -1,10 +1,104 @@
-use bevy::prelude::Entity;
-use bevy_ecs_macros::Event;
+use std::collections::HashMap;
+
+use bevy::prelude::{Component, Entity, Timer, TimerMode, Vec2};
+use bevy_ecs_macros::{Event, Resource};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::Deserialize;
+
+use crate::character::Character;
+use crate::status_effect::StatusEffectId;
+
+/// Kind of damage a hit deals. Every damage source carries one so that
+/// resistances can be applied to it.
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum DamageType {
+    #[default]
+    Physical,
+    Shadow,
+    Ice,
+    Fire,
+    Lightning,
+}
 
 #[derive(Event)]
 pub struct DamageDealtEvent{
     pub target: Entity,
     pub damage: u32,
+    /// Type of the damage, resisted by the target's [`ResistancesComponent`].
+    pub damage_type: DamageType,
+    /// Whether the hit was a critical strike.
+    pub is_critical: bool,
+    /// Entity responsible for the damage, if known (e.g. the attacking mob).
+    pub source: Option<Entity>,
+    /// Status effect applied to the target when the hit lands.
+    pub status_effect: Option<StatusEffectId>,
+    /// Force pushing the target away from the source.
+    pub knockback: f32,
+}
+
+/// Sent once a [`DamageDealtEvent`] has been resolved against the target's
+/// resistances and subtracted from its health.
+#[derive(Event, Debug)]
+pub struct DamageAppliedEvent {
+    pub target: Entity,
+    /// Damage actually taken after resistances.
+    pub damage: u32,
+    pub damage_type: DamageType,
+    /// Whether the hit was a critical strike, e.g. for damage number styling.
+    pub is_critical: bool,
+    pub source: Option<Entity>,
+}
+
+/// Sent whenever an entity regains health, e.g. from regeneration.
+#[derive(Event, Debug)]
+pub struct HealAppliedEvent {
+    pub target: Entity,
+    /// Health actually regained, capped by the target's max health.
+    pub amount: u32,
+}
+
+/// Pushes `target` along `direction` and briefly stuns it.
+/// Sent by the damage pipeline for hits with knockback.
+#[derive(Event, Debug)]
+pub struct KnockbackEvent {
+    pub target: Entity,
+    /// Normalized direction of the push.
+    pub direction: Vec2,
+    pub force: f32,
+}
+
+/// Fraction (0.0 to 1.0) of knockback force and hit-stun an entity ignores.
+/// Entities without it take the full knockback.
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct KnockbackResistanceComponent(pub f32);
+
+impl KnockbackResistanceComponent {
+    /// Returns the force left after resistance.
+    pub fn resolve(&self, force: f32) -> f32 {
+        force * (1.0 - self.0.clamp(0.0, 1.0))
+    }
+}
+
+/// Velocity of a mob being knocked back. Decays until it is removed.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct KnockbackComponent {
+    pub velocity: Vec2,
+}
+
+/// Present while an entity is stunned by a hit.
+/// Mob behavior systems and player movement input skip stunned entities.
+#[derive(Component, Clone, Debug)]
+pub struct HitStunComponent {
+    pub timer: Timer,
+}
+
+impl HitStunComponent {
+    pub fn new(seconds: f32) -> Self {
+        Self {
+            timer: Timer::from_seconds(seconds, TimerMode::Once),
+        }
+    }
 }
 
 /// Event to notify that a specific entity's health regeneration should reset.
@@ -12,4 +106,236 @@ pub struct DamageDealtEvent{
 #[derive(Event)]
 pub struct HealthRegainResetEvent {
     pub entity: Entity,
-}
\ No newline at end of file
+}
+
+/// Random number generator used for combat rolls (critical strikes, damage
+/// variance). Seeding it makes every roll reproducible.
+#[derive(Resource)]
+pub struct CombatRngResource(pub StdRng);
+
+impl CombatRngResource {
+    pub fn seeded(seed: u64) -> Self {
+        Self(StdRng::seed_from_u64(seed))
+    }
+}
+
+impl Default for CombatRngResource {
+    fn default() -> Self {
+        Self(StdRng::from_entropy())
+    }
+}
+
+/// Random range the damage of a hit is multiplied by
+/// (e.g. `min: 0.9, max: 1.1` = ±10%).
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct DamageVariance {
+    pub min: f32,
+    pub max: f32,
+}
+
+/// Damage of a single hit after rolling for critical strike and variance.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DamageRoll {
+    pub damage: u32,
+    pub is_critical: bool,
+}
+
+/// Critical strike and variance stats of an attacker.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CriticalStats {
+    /// Chance (0.0 to 1.0) that a hit is critical.
+    pub chance: f32,
+    /// Damage multiplier of critical hits.
+    pub multiplier: f32,
+    /// Optional random variance applied to every hit.
+    pub variance: Option<DamageVariance>,
+}
+
+impl Default for CriticalStats {
+    fn default() -> Self {
+        Self {
+            chance: 0.0,
+            multiplier: 1.0,
+            variance: None,
+        }
+    }
+}
+
+impl CriticalStats {
+    /// Rolls the damage of a hit with `base` damage.
+    pub fn roll(&self, base: u32, rng: &mut impl Rng) -> DamageRoll {
+        let mut damage = base as f32;
+        if let Some(variance) = self.variance {
+            if variance.max > variance.min {
+                damage *= rng.gen_range(variance.min..=variance.max);
+            } else {
+                damage *= variance.min;
+            }
+        }
+
+        let is_critical = rng.gen_bool(self.chance.clamp(0.0, 1.0) as f64);
+        if is_critical {
+            damage *= self.multiplier;
+        }
+
+        DamageRoll {
+            damage: damage.max(0.0).round() as u32,
+            is_critical,
+        }
+    }
+}
+
+impl From<&Character> for CriticalStats {
+    fn from(value: &Character) -> Self {
+        Self {
+            chance: value.crit_chance,
+            multiplier: value.crit_multiplier,
+            variance: value.damage_variance,
+        }
+    }
+}
+
+/// Resistance of an entity per damage type.
+///
+/// A resistance is the fraction of incoming damage that is blocked:
+/// `0.25` blocks a quarter, `1.0` (or more) makes the entity immune and
+/// negative values make it take extra damage (`-0.5` = 50% more).
+/// Damage types without an entry are taken in full.
+#[derive(Component, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(transparent)]
+pub struct ResistancesComponent {
+    pub resistances: HashMap<DamageType, f32>,
+}
+
+impl ResistancesComponent {
+    /// Returns the resistance against a damage type.
+    pub fn get(&self, damage_type: DamageType) -> f32 {
+        self.resistances.get(&damage_type).copied().unwrap_or(0.0)
+    }
+
+    /// Returns the damage taken from a hit of `damage` after resistances.
+    pub fn resolve(&self, damage: u32, damage_type: DamageType) -> u32 {
+        let multiplier = (1.0 - self.get(damage_type)).max(0.0);
+        (damage as f32 * multiplier).round() as u32
+    }
+}
+
+impl From<&Character> for ResistancesComponent {
+    fn from(value: &Character) -> Self {
+        value.resistances.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resistances(entries: &[(DamageType, f32)]) -> ResistancesComponent {
+        ResistancesComponent {
+            resistances: entries.iter().copied().collect(),
+        }
+    }
+
+    #[test]
+    fn missing_resistance_takes_full_damage() {
+        let resistances = resistances(&[(DamageType::Fire, 0.5)]);
+        assert_eq!(resistances.resolve(40, DamageType::Ice), 40);
+    }
+
+    #[test]
+    fn partial_resistance_reduces_damage() {
+        let resistances = resistances(&[(DamageType::Fire, 0.25)]);
+        assert_eq!(resistances.resolve(40, DamageType::Fire), 30);
+    }
+
+    #[test]
+    fn immunity_blocks_all_damage() {
+        let resistances = resistances(&[
+            (DamageType::Shadow, 1.0),
+            (DamageType::Fire, 1.5),
+        ]);
+        assert_eq!(resistances.resolve(40, DamageType::Shadow), 0);
+        assert_eq!(resistances.resolve(40, DamageType::Fire), 0);
+    }
+
+    #[test]
+    fn negative_resistance_increases_damage() {
+        let resistances = resistances(&[
+            (DamageType::Ice, -0.5),
+            (DamageType::Fire, -1.0),
+        ]);
+        assert_eq!(resistances.resolve(40, DamageType::Ice), 60);
+        assert_eq!(resistances.resolve(40, DamageType::Fire), 80);
+    }
+
+    #[test]
+    fn seeded_rolls_are_reproducible() {
+        let stats = CriticalStats {
+            chance: 0.5,
+            multiplier: 2.0,
+            variance: Some(DamageVariance { min: 0.8, max: 1.2 }),
+        };
+        let roll_all = |seed| {
+            let mut rng = CombatRngResource::seeded(seed);
+            (0..32)
+                .map(|_| stats.roll(100, &mut rng.0))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(roll_all(7), roll_all(7));
+        assert_ne!(roll_all(7), roll_all(8));
+    }
+
+    #[test]
+    fn critical_chance_bounds() {
+        let mut rng = CombatRngResource::seeded(1);
+        let never = CriticalStats {
+            chance: 0.0,
+            multiplier: 2.0,
+            variance: None,
+        };
+        let always = CriticalStats { chance: 1.0, ..never };
+
+        for _ in 0..32 {
+            assert_eq!(
+                never.roll(10, &mut rng.0),
+                DamageRoll {
+                    damage: 10,
+                    is_critical: false
+                }
+            );
+            assert_eq!(
+                always.roll(10, &mut rng.0),
+                DamageRoll {
+                    damage: 20,
+                    is_critical: true
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn variance_stays_within_range() {
+        let mut rng = CombatRngResource::seeded(3);
+        let stats = CriticalStats {
+            variance: Some(DamageVariance { min: 0.9, max: 1.1 }),
+            ..Default::default()
+        };
+
+        for _ in 0..64 {
+            let roll = stats.roll(100, &mut rng.0);
+            assert!((90..=110).contains(&roll.damage));
+            assert!(!roll.is_critical);
+        }
+    }
+
+    #[test]
+    fn resistances_deserialize_from_ron() {
+        let resistances: ResistancesComponent =
+            ron::de::from_str("{ Shadow: 1.0, Fire: -0.25 }").unwrap();
+        assert_eq!(resistances.get(DamageType::Shadow), 1.0);
+        assert_eq!(resistances.get(DamageType::Fire), -0.25);
+        assert_eq!(resistances.get(DamageType::Physical), 0.0);
+    }
+}
@@ -0,0 +1,19 @@
+use bevy::prelude::Entity;
+use bevy_ecs_macros::Event;
+
+/// Sent once an ability passed its cooldown check and was activated.
+/// Each registered ability reacts to the events whose `ability` entity carries its component.
+#[derive(Event, Debug)]
+pub struct AbilityActivatedEvent {
+    /// Entity that cast the ability (e.g. the player).
+    pub caster: Entity,
+    /// The ability entity (a child of the caster).
+    pub ability: Entity,
+}
+
+/// Requests raising an ability entity by one rank (e.g. from a level-up reward).
+#[derive(Event, Debug)]
+pub struct AbilityRankUpEvent {
+    /// The ability entity to rank up.
+    pub ability: Entity,
+}
@@ -15,6 +15,8 @@ pub struct DashEvent(pub Entity);
 pub struct LightAttackEvent(pub Entity);
 #[derive(Event, Debug)]
 pub struct HeavyAttackEvent(pub Entity);
+#[derive(Event, Debug)]
+pub struct FireEvent(pub Entity);
 
 #[derive(Event, Debug)]
 pub struct UseSkillEvent {
@@ -7,3 +7,13 @@ pub struct AnimationChangeEvent {
     pub entity: Entity,
     pub state: PlayerState,
 }
+
+/// Sent when an animated entity enters a frame tagged in its clip's
+/// `AnimationData::events`.
+#[derive(Event, Debug, Clone)]
+pub struct AnimationFrameEvent {
+    pub entity: Entity,
+    pub clip: PlayerState,
+    pub frame: usize,
+    pub tag: String,
+}
@@ -1,3 +1,5 @@
+pub mod ability;
 pub mod action;
 pub mod animation;
+pub mod reward;
 
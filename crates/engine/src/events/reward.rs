@@ -0,0 +1,39 @@
+use bevy::prelude::Entity;
+use bevy_ecs_macros::Event;
+
+use crate::passive::PassiveId;
+use crate::weapon::WeaponId;
+
+/// Where a claimed reward comes from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RewardSource {
+    Chest,
+    LevelUp,
+}
+
+/// A reward a player can claim.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Reward {
+    /// Adds the weapon to the inventory, or raises its level if held.
+    Weapon(WeaponId),
+    /// Adds the passive item, or raises its level if held.
+    Passive(PassiveId),
+}
+
+/// Sent when a player claims a chest or level-up reward. Weapon evolutions
+/// are checked once the reward is applied.
+#[derive(Event, Clone, Debug)]
+pub struct RewardClaimedEvent {
+    /// Player claiming the reward.
+    pub entity: Entity,
+    pub source: RewardSource,
+    pub reward: Reward,
+}
+
+/// Sent when a weapon of a player evolved into another one.
+#[derive(Event, Clone, Debug)]
+pub struct WeaponEvolvedEvent {
+    pub entity: Entity,
+    pub weapon: WeaponId,
+    pub evolved: WeaponId,
+}
@@ -49,8 +49,11 @@ impl HealthComponent {
     }
 
     /// Heals the entity by the specified amount, without exceeding max.
-    pub fn heal(&mut self, amount: u32) {
-        self.current = (self.current + amount).min(self.max);
+    /// Returns the health actually regained.
+    pub fn heal(&mut self, amount: u32) -> u32 {
+        let previous = self.current;
+        self.current = self.current.saturating_add(amount).min(self.max);
+        self.current - previous
     }
 
     /// Returns the fraction of health remaining (0.0 to 1.0).
@@ -67,6 +70,13 @@ impl HealthComponent {
     pub fn increase_max(&mut self, value: u32) {
         self.max = self.max.saturating_add(value);
     }
+
+    /// Permanently decreases the maximum health by `value`.
+    /// Current health is clamped to the new maximum.
+    pub fn decrease_max(&mut self, value: u32) {
+        self.max = self.max.saturating_sub(value);
+        self.current = self.current.min(self.max);
+    }
 }
 
 impl From<&Character> for HealthComponent {
@@ -108,15 +118,23 @@ impl HealthRegainComponent {
     /// Updates regeneration logic.
     /// - `delta`: Time since last frame.
     /// - `health`: Mutable reference to the entity's HealthComponent.
-    pub fn update(&mut self, delta: Duration, health: &mut HealthComponent) {
+    ///
+    /// Returns the health regained this frame.
+    pub fn update(
+        &mut self,
+        delta: Duration,
+        health: &mut HealthComponent,
+    ) -> u32 {
         self.delay_timer.tick(delta);
         if !self.delay_timer.finished() {
-            return;
+            return 0;
         }
 
         self.interval_timer.tick(delta);
         if self.interval_timer.just_finished() {
-            health.heal(self.amount_per_tick);
+            health.heal(self.amount_per_tick)
+        } else {
+            0
         }
     }
 }
@@ -38,6 +38,9 @@ pub enum PlayerAction {
     /// Heavy Attack
     HeavyAttack,
 
+    /// Fire projectiles towards the cursor
+    Fire,
+
     /// Use the first ability (Slot 1)
     SlotOneAbility,
 
@@ -46,6 +49,9 @@ pub enum PlayerAction {
 
     /// Use the third ability (Slot 3)
     SlotThreeAbility,
+
+    /// Prefer the next extracted shadow for `ShadowSummon`
+    CycleShadowPreference,
 }
 
 /// The parsed input/key bindings used for the life of the  entire game. This is read from files/
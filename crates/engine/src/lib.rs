@@ -1,4 +1,5 @@
 pub mod abilities;
+pub mod aim;
 pub mod animation;
 pub mod character;
 pub mod character_selection;
@@ -6,11 +7,16 @@ pub mod collision;
 pub mod combat;
 pub mod health;
 pub mod input;
+pub mod mana;
+pub mod melee;
 pub mod objective;
+pub mod passive;
 pub mod player;
 pub mod run;
+pub mod settings;
 pub mod spawnable;
 pub mod states;
+pub mod status_effect;
 pub mod weapon;
 pub mod events;
 
@@ -0,0 +1,116 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::character::Character;
+
+/// Component that tracks current and maximum mana for an entity.
+/// Abilities with a mana cost spend from it when cast.
+#[derive(Component)]
+pub struct ManaComponent {
+    /// Current mana value. Always <= `max`.
+    pub current: u32,
+    /// Maximum mana value.
+    pub max: u32,
+}
+
+impl ManaComponent {
+    /// Creates a new ManaComponent with the given maximum mana.
+    /// The entity starts with full mana.
+    pub fn new(max_mana: u32) -> Self {
+        Self {
+            current: max_mana,
+            max: max_mana,
+        }
+    }
+
+    /// Returns `true` if at least `amount` mana is available.
+    pub fn has(&self, amount: u32) -> bool {
+        self.current >= amount
+    }
+
+    /// Spends `amount` mana if enough is available.
+    /// Returns `false` and leaves mana untouched otherwise.
+    pub fn spend(&mut self, amount: u32) -> bool {
+        if !self.has(amount) {
+            return false;
+        }
+        self.current -= amount;
+        true
+    }
+
+    /// Restores mana by the specified amount, without exceeding max.
+    pub fn restore(&mut self, amount: u32) {
+        self.current = self.current.saturating_add(amount).min(self.max);
+    }
+
+    /// Returns the fraction of mana remaining (0.0 to 1.0).
+    pub fn mana_percentage(&self) -> f32 {
+        if self.max > 0 {
+            self.current as f32 / self.max as f32
+        } else {
+            0.0
+        }
+    }
+}
+
+impl From<&Character> for ManaComponent {
+    fn from(value: &Character) -> Self {
+        ManaComponent::new(value.mana)
+    }
+}
+
+/// Component that handles passive mana regeneration after mana was spent.
+#[derive(Component)]
+pub struct ManaRegainComponent {
+    /// Delay (in seconds) after the last cast before regeneration starts.
+    pub delay_timer: Timer,
+    /// Interval (in seconds) between each regeneration tick.
+    pub interval_timer: Timer,
+    /// Amount of mana to regenerate each tick.
+    pub amount_per_tick: u32,
+}
+
+impl Default for ManaRegainComponent {
+    fn default() -> Self {
+        Self {
+            // Wait 1s after the last cast before regen begins
+            delay_timer: Timer::from_seconds(1.0, TimerMode::Once),
+            // Once started, regen occurs every 0.5s
+            interval_timer: Timer::from_seconds(0.5, TimerMode::Repeating),
+            amount_per_tick: 1,
+        }
+    }
+}
+
+impl From<&Character> for ManaRegainComponent {
+    fn from(value: &Character) -> Self {
+        Self {
+            amount_per_tick: value.mana_regen,
+            ..Default::default()
+        }
+    }
+}
+
+impl ManaRegainComponent {
+    /// Resets both timers, called when the entity spends mana.
+    pub fn reset(&mut self) {
+        self.delay_timer.reset();
+        self.interval_timer.reset();
+    }
+
+    /// Updates regeneration logic.
+    /// - `delta`: Time since last frame.
+    /// - `mana`: Mutable reference to the entity's ManaComponent.
+    pub fn update(&mut self, delta: Duration, mana: &mut ManaComponent) {
+        self.delay_timer.tick(delta);
+        if !self.delay_timer.finished() {
+            return;
+        }
+
+        self.interval_timer.tick(delta);
+        if self.interval_timer.just_finished() {
+            mana.restore(self.amount_per_tick);
+        }
+    }
+}
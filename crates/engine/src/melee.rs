@@ -0,0 +1,367 @@
+use std::collections::HashSet;
+use std::time::Duration;
+
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::character::Character;
+use crate::combat::{CriticalStats, DamageType};
+
+/// Frame tag starting a swing of the light attack clip.
+pub const SWING_FRAME_TAG: &str = "swing";
+
+/// Frame tag on which a melee swing can spawn its hitbox. Attacks spawn it on
+/// the tagged frame matching the first of their `active_frames`.
+pub const HITBOX_FRAME_TAG: &str = "hitbox";
+
+/// Data of a melee swing, e.g. the player's light attack.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct MeleeAttackData {
+    /// Reach of the swing from the attacker's center.
+    pub range: f32,
+    /// Full angle (in radians) of the arc covered by the swing.
+    pub arc: f32,
+    /// First and last frame (inclusive) of the clip on which the hitbox is active.
+    /// The first one must be tagged with [`HITBOX_FRAME_TAG`] in
+    /// `animations.ron`.
+    pub active_frames: (usize, usize),
+    /// Multiplier applied to the attacker's weapon damage.
+    #[serde(default = "default_damage_multiplier")]
+    pub damage_multiplier: f32,
+    #[serde(default)]
+    pub damage_type: DamageType,
+    #[serde(default)]
+    pub knockback: f32,
+}
+
+fn default_damage_multiplier() -> f32 {
+    1.0
+}
+
+/// Data of the light attack combo.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct LightAttackData {
+    /// Swings of the combo, performed in order. Must not be empty.
+    pub combo: Vec<MeleeAttackData>,
+    /// Seconds after a swing during which the next light attack continues
+    /// the combo instead of starting over.
+    pub combo_window: f32,
+}
+
+/// Light attack of a player and the state of its combo.
+#[derive(Component, Clone, Debug)]
+pub struct LightAttackComponent {
+    pub data: LightAttackData,
+    /// Combo step performed by the next swing.
+    pub step: usize,
+    /// Combo step of the swing in progress, if any.
+    pub swing_step: Option<usize>,
+    /// Whether the hitbox of the current swing was already spawned.
+    pub hitbox_spawned: bool,
+    /// Time left to continue the combo after the last swing.
+    pub combo_timer: Timer,
+}
+
+impl LightAttackComponent {
+    /// Swing of the combo step in progress, or of the next one.
+    pub fn attack(&self) -> &MeleeAttackData {
+        &self.data.combo[self.swing_step.unwrap_or(self.step)]
+    }
+
+    /// Starts a swing of the current combo step.
+    pub fn start_swing(&mut self) {
+        self.swing_step = Some(self.step);
+        self.hitbox_spawned = false;
+    }
+
+    /// Ends the swing in progress. The combo advances to the next step,
+    /// unless it was reset during the swing.
+    pub fn finish_swing(&mut self) {
+        if self.swing_step == Some(self.step) {
+            self.step = (self.step + 1) % self.data.combo.len().max(1);
+        }
+        self.combo_timer.reset();
+        self.swing_step = None;
+        self.hitbox_spawned = false;
+    }
+
+    /// Called when the player stops attacking. The swing only counts
+    /// towards the combo if its hitbox was already spawned.
+    pub fn stop(&mut self) {
+        if self.swing_step.is_some() && self.hitbox_spawned {
+            self.finish_swing();
+        } else {
+            self.swing_step = None;
+            self.hitbox_spawned = false;
+        }
+    }
+
+    /// Starts the combo over from the first step.
+    pub fn reset_combo(&mut self) {
+        self.step = 0;
+        self.combo_timer.reset();
+    }
+
+    /// Advances the combo window, resetting the combo once it runs out.
+    pub fn tick_combo(&mut self, delta: Duration) {
+        if self.step != 0 && self.combo_timer.tick(delta).finished() {
+            self.reset_combo();
+        }
+    }
+}
+
+impl From<&Character> for LightAttackComponent {
+    fn from(value: &Character) -> Self {
+        Self {
+            data: value.light_attack.clone(),
+            step: 0,
+            swing_step: None,
+            hitbox_spawned: false,
+            combo_timer: Timer::from_seconds(
+                value.light_attack.combo_window,
+                TimerMode::Once,
+            ),
+        }
+    }
+}
+
+/// Data of a charged heavy attack.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct HeavyAttackData {
+    /// Swing performed on release, at no charge.
+    pub attack: MeleeAttackData,
+    /// Seconds of holding needed to reach full charge.
+    pub charge_time: f32,
+    /// Damage multiplier reached at full charge (scales `attack.damage_multiplier`).
+    pub max_damage_multiplier: f32,
+    /// Range multiplier reached at full charge.
+    pub max_range_multiplier: f32,
+    /// Multiplier applied to movement speed while charging.
+    pub charging_speed_multiplier: f32,
+    /// Seconds the release animation plays.
+    pub release_duration: f32,
+}
+
+/// Heavy attack of a player and the state of its charge.
+#[derive(Component, Clone, Debug)]
+pub struct HeavyAttackComponent {
+    pub data: HeavyAttackData,
+    /// Current charge, from 0.0 to 1.0.
+    pub charge: f32,
+    /// Charge of the attack being released, if any.
+    pub released_charge: Option<f32>,
+    /// Remaining time of the release animation.
+    pub release_timer: Timer,
+    /// Whether the hitbox of the release was already spawned.
+    pub hitbox_spawned: bool,
+}
+
+impl HeavyAttackComponent {
+    /// Returns `true` while the release animation plays.
+    pub fn is_releasing(&self) -> bool {
+        self.released_charge.is_some()
+    }
+
+    /// Grows the charge by `delta` seconds of holding.
+    pub fn add_charge(&mut self, delta: f32) {
+        let charge_time = self.data.charge_time.max(f32::EPSILON);
+        self.charge = (self.charge + delta / charge_time).min(1.0);
+    }
+
+    /// Releases the current charge and starts the release animation.
+    pub fn release(&mut self) {
+        self.released_charge = Some(self.charge);
+        self.charge = 0.0;
+        self.hitbox_spawned = false;
+        self.release_timer.reset();
+    }
+
+    /// Damage multiplier of a release with the given charge.
+    pub fn damage_multiplier(&self, charge: f32) -> f32 {
+        self.data.attack.damage_multiplier
+            * (1.0 + (self.data.max_damage_multiplier - 1.0) * charge)
+    }
+
+    /// Range of a release with the given charge.
+    pub fn range(&self, charge: f32) -> f32 {
+        self.data.attack.range
+            * (1.0 + (self.data.max_range_multiplier - 1.0) * charge)
+    }
+}
+
+impl From<&Character> for HeavyAttackComponent {
+    fn from(value: &Character) -> Self {
+        Self {
+            data: value.heavy_attack.clone(),
+            charge: 0.0,
+            released_charge: None,
+            release_timer: Timer::from_seconds(
+                value.heavy_attack.release_duration,
+                TimerMode::Once,
+            ),
+            hitbox_spawned: false,
+        }
+    }
+}
+
+/// Short-lived arc shaped hitbox spawned by a melee swing.
+/// Every target is hit at most once during the hitbox's lifetime.
+#[derive(Component, Debug)]
+pub struct MeleeHitboxComponent {
+    /// Entity that performed the swing.
+    pub owner: Entity,
+    /// Normalized direction the arc is centered on.
+    pub direction: Vec2,
+    pub range: f32,
+    /// Half of the arc's angle (in radians).
+    pub half_arc: f32,
+    pub damage: u32,
+    pub damage_type: DamageType,
+    pub knockback: f32,
+    pub critical: CriticalStats,
+    /// Remaining time the hitbox stays active.
+    pub lifetime: Timer,
+    /// Entities already hit by this swing.
+    pub hit: HashSet<Entity>,
+}
+
+impl MeleeHitboxComponent {
+    /// Returns `true` if `target` lies inside the arc centered at `origin`.
+    pub fn contains(&self, origin: Vec2, target: Vec2) -> bool {
+        let offset = target - origin;
+        let distance = offset.length();
+        if distance > self.range {
+            return false;
+        }
+        if distance <= f32::EPSILON {
+            return true;
+        }
+
+        self.direction.angle_to(offset).abs() <= self.half_arc
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::FRAC_PI_4;
+
+    use super::*;
+
+    /// Light attack with a four step combo and a 0.5s combo window.
+    fn light_attack() -> LightAttackComponent {
+        let swing = MeleeAttackData {
+            range: 90.0,
+            arc: 2.0,
+            active_frames: (1, 2),
+            damage_multiplier: 1.0,
+            damage_type: DamageType::Physical,
+            knockback: 0.0,
+        };
+        LightAttackComponent {
+            data: LightAttackData {
+                combo: vec![swing; 4],
+                combo_window: 0.5,
+            },
+            step: 0,
+            swing_step: None,
+            hitbox_spawned: false,
+            combo_timer: Timer::from_seconds(0.5, TimerMode::Once),
+        }
+    }
+
+    /// Performs a full swing of the current combo step.
+    fn swing(light_attack: &mut LightAttackComponent) {
+        light_attack.start_swing();
+        light_attack.hitbox_spawned = true;
+        light_attack.finish_swing();
+    }
+
+    #[test]
+    fn combo_advances_through_every_step_and_wraps() {
+        let mut light_attack = light_attack();
+        let mut steps = vec![];
+        for _ in 0..5 {
+            steps.push(light_attack.step);
+            swing(&mut light_attack);
+        }
+        assert_eq!(steps, [0, 1, 2, 3, 0]);
+        assert_eq!(light_attack.step, 1);
+    }
+
+    #[test]
+    fn stopping_before_the_hitbox_does_not_advance_the_combo() {
+        let mut light_attack = light_attack();
+
+        light_attack.start_swing();
+        light_attack.stop();
+        assert_eq!(light_attack.step, 0);
+        assert_eq!(light_attack.swing_step, None);
+
+        light_attack.start_swing();
+        light_attack.hitbox_spawned = true;
+        light_attack.stop();
+        assert_eq!(light_attack.step, 1);
+        assert_eq!(light_attack.swing_step, None);
+    }
+
+    #[test]
+    fn combo_resets_once_the_combo_window_runs_out() {
+        let mut light_attack = light_attack();
+        swing(&mut light_attack);
+        swing(&mut light_attack);
+
+        light_attack.tick_combo(Duration::from_secs_f32(0.3));
+        assert_eq!(light_attack.step, 2);
+        light_attack.tick_combo(Duration::from_secs_f32(0.3));
+        assert_eq!(light_attack.step, 0);
+
+        // Every swing opens a new window
+        swing(&mut light_attack);
+        light_attack.tick_combo(Duration::from_secs_f32(0.3));
+        swing(&mut light_attack);
+        light_attack.tick_combo(Duration::from_secs_f32(0.3));
+        assert_eq!(light_attack.step, 2);
+    }
+
+    #[test]
+    fn swing_interrupted_by_a_reset_does_not_advance_the_combo() {
+        let mut light_attack = light_attack();
+        swing(&mut light_attack);
+        swing(&mut light_attack);
+
+        light_attack.start_swing();
+        light_attack.reset_combo();
+        light_attack.finish_swing();
+        assert_eq!(light_attack.step, 0);
+    }
+
+    #[test]
+    fn hitbox_contains_targets_within_range_and_arc() {
+        let hitbox = MeleeHitboxComponent {
+            owner: Entity::PLACEHOLDER,
+            direction: Vec2::X,
+            range: 100.0,
+            half_arc: FRAC_PI_4,
+            damage: 10,
+            damage_type: DamageType::Physical,
+            knockback: 0.0,
+            critical: CriticalStats::default(),
+            lifetime: Timer::from_seconds(0.1, TimerMode::Once),
+            hit: HashSet::new(),
+        };
+        let origin = Vec2::new(50.0, 50.0);
+
+        assert!(hitbox.contains(origin, origin));
+        assert!(hitbox.contains(origin, origin + Vec2::new(100.0, 0.0)));
+        assert!(hitbox.contains(origin, origin + Vec2::new(50.0, 40.0)));
+        // Too far away
+        assert!(!hitbox.contains(origin, origin + Vec2::new(101.0, 0.0)));
+        // Outside of the arc, on either side and behind
+        assert!(!hitbox.contains(origin, origin + Vec2::new(40.0, 50.0)));
+        assert!(!hitbox.contains(origin, origin + Vec2::new(40.0, -50.0)));
+        assert!(!hitbox.contains(origin, origin + Vec2::new(-10.0, 0.0)));
+    }
+}
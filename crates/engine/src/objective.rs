@@ -2,11 +2,15 @@ use bevy::log;
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 
+use crate::spawnable::mob::EnemyMobType;
+
 /// Event triggered when an entity (e.g., an enemy) is defeated.
 #[derive(Event)]
 pub struct EnemyDefeatedEvent {
 	pub is_boss: bool,
 	pub entity: Entity,
+	/// Type of the defeated enemy.
+	pub mob_type: EnemyMobType,
 }
 
 /// Event triggered when the player dies.
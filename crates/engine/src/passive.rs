@@ -0,0 +1,70 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use bevy::prelude::*;
+use serde::Deserialize;
+
+/// Unique identifier of a passive item (e.g. `"ShadowTome"`).
+/// `passives.ron` is keyed by it.
+#[derive(Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
+#[serde(transparent)]
+pub struct PassiveId(pub String);
+
+impl PassiveId {
+    pub fn new(id: impl Into<String>) -> Self {
+        Self(id.into())
+    }
+}
+
+impl fmt::Display for PassiveId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Data of a passive item.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct PassiveData {
+    /// Display name of the passive item.
+    pub name: String,
+    /// Highest level the passive item can reach.
+    pub max_level: u32,
+}
+
+/// Every passive item, loaded from `passives.ron`.
+#[derive(Resource, Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct PassivesResource {
+    pub passives: HashMap<PassiveId, PassiveData>,
+}
+
+impl PassivesResource {
+    pub fn get(&self, id: &PassiveId) -> Option<&PassiveData> {
+        self.passives.get(id)
+    }
+}
+
+/// Passive items of a player and their levels.
+#[derive(Component, Clone, Debug, Default)]
+pub struct PassiveInventoryComponent {
+    pub levels: HashMap<PassiveId, u32>,
+}
+
+impl PassiveInventoryComponent {
+    /// Level of the passive item `id`, 0 if the player does not hold it.
+    pub fn level(&self, id: &PassiveId) -> u32 {
+        self.levels.get(id).copied().unwrap_or(0)
+    }
+
+    /// Adds the passive item `id` or raises its level, up to `max_level`.
+    /// Returns `true` if the level changed.
+    pub fn add(&mut self, id: PassiveId, max_level: u32) -> bool {
+        let level = self.levels.entry(id).or_default();
+        if *level >= max_level {
+            return false;
+        }
+        *level += 1;
+        true
+    }
+}
@@ -1,6 +1,9 @@
 use bevy::prelude::*;
 
 use crate::character::{Character, CharacterType};
+use crate::combat::CriticalStats;
+use crate::spawnable::projectile::behavior::ProjectileId;
+use crate::spawnable::SpawnPosition;
 
 /// Stores all active and potential player slots.
 #[derive(Resource, Debug, Default)]
@@ -97,6 +100,12 @@ pub struct PlayerOutgoingDamageComponent {
 	pub projectile_size: f32,
 	/// Number of projectiles fired per ability activation.
 	pub projectile_count: u32,
+	/// Kind of projectile fired.
+	pub projectile: ProjectileId,
+	/// Offset from which projectiles spawn.
+	pub projectile_spawn_position: SpawnPosition,
+	/// Critical strike chance, multiplier and damage variance.
+	pub critical: CriticalStats,
 }
 
 impl From<&Character> for PlayerOutgoingDamageComponent {
@@ -108,6 +117,9 @@ impl From<&Character> for PlayerOutgoingDamageComponent {
 			projectile_despawn_time: character.projectile_despawn_time,
 			projectile_size: character.projectile_size,
 			projectile_count: character.projectile_count,
+			projectile: character.projectile.clone(),
+			projectile_spawn_position: character.projectile_spawn_position.clone(),
+			critical: CriticalStats::from(character),
 		}
 	}
 }
@@ -0,0 +1,114 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::aim::AimMode;
+
+/// Options chosen by the player, persisted across runs.
+///
+/// Every field has a default, so settings saved by an older version still
+/// load.
+#[derive(Resource, Deserialize, Serialize, Clone, Debug)]
+#[serde(default)]
+pub struct SettingsResource {
+    pub aim_mode: AimMode,
+    /// Whether damage and healing are shown as floating numbers.
+    pub damage_numbers: bool,
+    pub hit_feedback: HitFeedbackSettings,
+    pub volume: VolumeSettings,
+}
+
+impl Default for SettingsResource {
+    fn default() -> Self {
+        Self {
+            aim_mode: AimMode::default(),
+            damage_numbers: true,
+            hit_feedback: HitFeedbackSettings::default(),
+            volume: VolumeSettings::default(),
+        }
+    }
+}
+
+/// Feedback given when damage is applied: a tint flash on the target, a
+/// short freeze of the game on heavy hits and a shake of the camera.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct HitFeedbackSettings {
+    /// Whether hit targets flash.
+    pub flash: bool,
+    /// Tint of the target's sprite while it flashes.
+    pub flash_color: Color,
+    /// Seconds a hit target flashes.
+    pub flash_duration: f32,
+    /// Whether heavy hits freeze the game.
+    pub hit_stop: bool,
+    /// Damage from which a hit is heavy. Critical hits are always heavy.
+    pub heavy_hit_damage: u32,
+    /// Frames the game freezes for on a heavy hit.
+    pub hit_stop_frames: u32,
+    /// Whether hits shake the camera.
+    pub screen_shake: bool,
+    /// Multiplier of the shake, from 0 (none) to 1 (full).
+    pub shake_intensity: f32,
+    /// Trauma added per point of damage dealt to a mob.
+    pub trauma_per_damage: f32,
+    /// Trauma added whenever a player gets hit.
+    pub player_hit_trauma: f32,
+    /// Trauma removed per second.
+    pub trauma_decay: f32,
+    /// Offset of the camera at full trauma, in world units.
+    pub max_shake_offset: f32,
+    /// Rotation of the camera at full trauma, in radians.
+    pub max_shake_angle: f32,
+}
+
+impl Default for HitFeedbackSettings {
+    fn default() -> Self {
+        Self {
+            flash: true,
+            flash_color: Color::srgb(1.0, 0.35, 0.35),
+            flash_duration: 0.1,
+            hit_stop: true,
+            heavy_hit_damage: 40,
+            hit_stop_frames: 4,
+            screen_shake: true,
+            shake_intensity: 1.0,
+            trauma_per_damage: 0.005,
+            player_hit_trauma: 0.4,
+            trauma_decay: 1.5,
+            max_shake_offset: 12.0,
+            max_shake_angle: 0.04,
+        }
+    }
+}
+
+/// Volume of the audio channels, each from 0 (muted) to 1 (full).
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq)]
+#[serde(default)]
+pub struct VolumeSettings {
+    /// Applied on top of every other volume.
+    pub master: f32,
+    pub music: f32,
+    pub sfx: f32,
+}
+
+impl Default for VolumeSettings {
+    fn default() -> Self {
+        Self {
+            master: 1.0,
+            music: 0.6,
+            sfx: 0.8,
+        }
+    }
+}
+
+impl VolumeSettings {
+    /// Volume of the music channel.
+    pub fn music(&self) -> f64 {
+        (self.master * self.music).clamp(0.0, 1.0) as f64
+    }
+
+    /// Volume of the sound effects channel.
+    pub fn sfx(&self) -> f64 {
+        (self.master * self.sfx).clamp(0.0, 1.0) as f64
+    }
+}
@@ -1,8 +1,16 @@
+pub mod shadow;
+
+use std::collections::HashMap;
+
+use crate::combat::{
+	DamageType, KnockbackResistanceComponent, ResistancesComponent,
+};
+use crate::spawnable::projectile::behavior::ProjectileId;
 use crate::spawnable::Faction;
 use bevy::math::{Quat, Vec2};
-use bevy::prelude::Entity;
-use bevy_ecs_macros::Event;
-use serde::Deserialize;
+use bevy::prelude::{Entity, Timer, TimerMode};
+use bevy_ecs_macros::{Component, Event, Resource};
+use serde::{Deserialize, Serialize};
 use strum_macros::{Display, EnumString};
 
 /// Enemy types from the game world.
@@ -20,12 +28,17 @@ use strum_macros::{Display, EnumString};
 )]
 pub enum EnemyMobType {
 	Goblin,
-	// Future: Orc, Elf, Knight, etc.
+	/// Goblin shooting arrows from a distance.
+	GoblinArcher,
+	Knight,
+	AntKing,
+	// Future: Orc, Elf, etc.
 }
 
 /// Allied mobs, such as summons or friendly NPCs.
 #[derive(
 	Deserialize,
+	Serialize,
 	EnumString,
 	Display,
 	Debug,
@@ -37,15 +50,31 @@ pub enum EnemyMobType {
 )]
 pub enum AllyMobType {
 	Soldier,
+	/// Generic shadow raised when no extracted shadow is available.
 	ShadowSummon,
+	/// Shadow extracted from goblins.
+	ShadowGoblin,
+	/// Shadow extracted from regular knights.
+	ShadowKnight,
+	/// Named shadow extracted from a knight boss.
+	Igris,
+	/// Named shadow extracted from the ant king.
+	Beru,
 	// Future: Healer, Mage, etc.
 }
 
+impl AllyMobType {
+	/// Returns `true` for allies raised by `ShadowSummon`.
+	pub fn is_shadow(&self) -> bool {
+		!matches!(self, AllyMobType::Soldier)
+	}
+}
+
 /// A general representation of all mobs in the game.
 /// Distinguishes between enemy and ally variants.
 #[derive(Deserialize, Debug, Hash, PartialEq, Eq, Clone, Display)]
 pub enum MobType {
-	// Enemy(EnemyMobType),
+	Enemy(EnemyMobType),
 	Ally(AllyMobType),
 }
 
@@ -53,7 +82,7 @@ impl MobType {
 	/// Returns the faction of the mob (Enemy or Ally).
 	pub fn get_faction(&self) -> Faction {
 		match self {
-			// MobType::Enemy(_) => Faction::Enemy,
+			MobType::Enemy(_) => Faction::Enemy,
 			MobType::Ally(_) => Faction::Ally,
 		}
 	}
@@ -61,7 +90,7 @@ impl MobType {
 	/// Returns the display name of the mob as a `String`.
 	pub fn get_name(&self) -> String {
 		match self {
-			// MobType::Enemy(enemy) => enemy.to_string(),
+			MobType::Enemy(enemy) => enemy.to_string(),
 			MobType::Ally(ally) => ally.to_string(),
 		}
 	}
@@ -82,3 +111,174 @@ pub struct SpawnMobEvent {
 	/// Optional summoner entity (e.g. the player who summoned it)
 	pub summoned_by: Option<Entity>,
 }
+
+/// Tag component for hostile mobs that players and their allies can target.
+#[derive(Component)]
+pub struct EnemyComponent;
+
+/// Type of an enemy, reported when it is defeated.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct EnemyTypeComponent {
+	pub mob_type: EnemyMobType,
+	/// Whether the enemy was spawned as a boss
+	pub boss: bool,
+}
+
+/// Combat stats shared by every mob of a type.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct MobStats {
+	/// Type of the damage the mob deals.
+	#[serde(default)]
+	pub damage_type: DamageType,
+	/// Resistance per damage type.
+	#[serde(default)]
+	pub resistances: ResistancesComponent,
+	/// Fraction of knockback and hit-stun the mob ignores.
+	#[serde(default)]
+	pub knockback_resistance: f32,
+	/// Maximum health of enemies of this type. Allies derive their health
+	/// from their summoner instead.
+	#[serde(default = "default_max_health")]
+	pub max_health: u32,
+	/// Projectiles the mob fires at its targets, if it attacks from range.
+	#[serde(default)]
+	pub ranged_attack: Option<RangedAttackData>,
+}
+
+fn default_max_health() -> u32 {
+	100
+}
+
+impl Default for MobStats {
+	fn default() -> Self {
+		Self {
+			damage_type: DamageType::default(),
+			resistances: ResistancesComponent::default(),
+			knockback_resistance: 0.0,
+			max_health: default_max_health(),
+			ranged_attack: None,
+		}
+	}
+}
+
+/// Data of a mob's ranged attack.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct RangedAttackData {
+	/// Seconds between two shots.
+	pub fire_interval: f32,
+	/// Distance within which the mob fires at a target.
+	pub range: f32,
+	/// Kind of the projectiles fired, from `projectiles.ron`.
+	pub projectile: ProjectileId,
+	pub damage: u32,
+	/// Speed of the projectiles in units per second.
+	pub projectile_speed: f32,
+	/// Seconds before the projectiles despawn.
+	pub projectile_lifetime: f32,
+	/// Projectiles fired per shot, spread around the aimed direction.
+	#[serde(default = "default_projectile_count")]
+	pub projectile_count: u32,
+}
+
+fn default_projectile_count() -> u32 {
+	1
+}
+
+/// Ranged attack of a mob, firing every `fire_interval` while a target is
+/// in range.
+#[derive(Component, Clone, Debug)]
+pub struct RangedAttackComponent {
+	pub data: RangedAttackData,
+	pub damage_type: DamageType,
+	/// Cooldown until the next shot. It stays finished while no target is in
+	/// range, so the mob fires as soon as one comes close.
+	pub fire_timer: Timer,
+}
+
+/// Knockback resistance bosses have at least, making them mostly immune.
+pub const BOSS_KNOCKBACK_RESISTANCE: f32 = 0.9;
+
+impl MobStats {
+	/// Knockback resistance of the mob, raised for bosses.
+	pub fn knockback_resistance(
+		&self,
+		boss: bool,
+	) -> KnockbackResistanceComponent {
+		if boss {
+			KnockbackResistanceComponent(
+				self.knockback_resistance.max(BOSS_KNOCKBACK_RESISTANCE),
+			)
+		} else {
+			KnockbackResistanceComponent(self.knockback_resistance)
+		}
+	}
+
+	/// Ranged attack of the mob, if it has one.
+	pub fn ranged_attack(&self) -> Option<RangedAttackComponent> {
+		self.ranged_attack.as_ref().map(|data| RangedAttackComponent {
+			data: data.clone(),
+			damage_type: self.damage_type,
+			fire_timer: Timer::from_seconds(
+				data.fire_interval,
+				TimerMode::Once,
+			),
+		})
+	}
+}
+
+/// Stats of every mob type, loaded from `mob_stats.ron`.
+#[derive(Resource, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MobStatsResource {
+	pub enemies: HashMap<EnemyMobType, MobStats>,
+	pub allies: HashMap<AllyMobType, MobStats>,
+}
+
+impl MobStatsResource {
+	/// Returns the stats of an allied mob type.
+	pub fn ally(&self, mob_type: AllyMobType) -> Option<&MobStats> {
+		self.allies.get(&mob_type)
+	}
+
+	/// Returns the stats of an enemy mob type.
+	pub fn enemy(&self, mob_type: EnemyMobType) -> Option<&MobStats> {
+		self.enemies.get(&mob_type)
+	}
+}
+
+/// Enemies spawned around the players during a run, loaded from `mobs.ron`.
+#[derive(Resource, Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct MobSpawnsResource {
+	/// Seconds between two enemy spawns.
+	pub spawn_interval: f32,
+	/// Distance from the player at which enemies appear.
+	pub spawn_distance: f32,
+	/// No enemy spawns while this many are alive.
+	pub max_enemies: usize,
+	/// Enemies picked at random on every spawn.
+	pub enemies: Vec<EnemySpawnData>,
+	/// Bosses spawned once each, when the run reaches their time.
+	#[serde(default)]
+	pub bosses: Vec<BossSpawnData>,
+}
+
+/// An enemy type of the spawn table and its chance to be picked.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct EnemySpawnData {
+	pub mob_type: EnemyMobType,
+	/// Weight of the type against the other entries.
+	pub weight: u32,
+}
+
+/// A boss spawned once during a run.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct BossSpawnData {
+	pub mob_type: EnemyMobType,
+	/// Seconds into the run at which the boss spawns.
+	pub time: f32,
+}
@@ -0,0 +1,334 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::combat::{CriticalStats, DamageType};
+use crate::spawnable::mob::{AllyMobType, EnemyMobType};
+use crate::status_effect::StatusEffectId;
+
+/// Rule used by allied shadows to pick the enemy they attack.
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum ShadowTargetPolicy {
+	/// Closest enemy to the shadow.
+	#[default]
+	Nearest,
+	/// Enemy with the least current health.
+	LowestHealth,
+	/// Enemy that most recently damaged the summoner.
+	/// Falls back to `Nearest` while nobody is attacking.
+	AttackingSummoner,
+}
+
+/// Behavior state of an allied shadow soldier raised by `ShadowSummon`.
+///
+/// Shadows follow their summoner, never stray further than `max_radius` from it
+/// (leashing back when they do) and attack targets chosen by `target_policy`.
+#[derive(Component, Debug)]
+pub struct ShadowSoldierComponent {
+	/// Which shadow this is.
+	pub mob_type: AllyMobType,
+	/// Entity that summoned this shadow.
+	pub summoned_by: Entity,
+	/// Maximum distance from the summoner the shadow may go.
+	pub max_radius: f32,
+	/// Movement speed (units per second).
+	pub speed: f32,
+	/// Distance at which the shadow can hit its target.
+	pub attack_range: f32,
+	/// Damage dealt per hit, already scaled by the summon's `damage_percentage`.
+	pub damage: u32,
+	/// Type of the damage dealt per hit.
+	pub damage_type: DamageType,
+	/// Critical strike stats, inherited from the summoner.
+	pub critical: CriticalStats,
+	/// Cooldown until the next hit. It stays finished while the target is out
+	/// of range, so the shadow hits as soon as it gets close.
+	pub attack_timer: Timer,
+	/// Status effect applied by every hit.
+	pub on_hit_status_effect: Option<StatusEffectId>,
+	/// Knockback force of every hit.
+	pub knockback: f32,
+	/// How targets are picked.
+	pub target_policy: ShadowTargetPolicy,
+	/// Current target, if any.
+	pub target: Option<Entity>,
+	/// Whether the shadow is returning to its summoner after drifting out of range.
+	pub leashing: bool,
+	/// Optional timer tracking the shadow's lifetime. If None, it is permanent.
+	pub lifetime: Option<Timer>,
+}
+
+impl ShadowSoldierComponent {
+	/// Distance to the summoner the shadow keeps while it has nothing to attack.
+	pub fn follow_distance(&self) -> f32 {
+		self.max_radius * 0.5
+	}
+}
+
+/// Rank of an extracted shadow, loosely following the Solo Leveling grades.
+/// Higher ranks get stronger stats when summoned.
+#[derive(
+	Deserialize,
+	Serialize,
+	Clone,
+	Copy,
+	Debug,
+	PartialEq,
+	Eq,
+	PartialOrd,
+	Ord,
+	Hash,
+	Default
+)]
+pub enum ShadowRank {
+	#[default]
+	Normal,
+	Elite,
+	Knight,
+	General,
+	Marshal,
+}
+
+impl ShadowRank {
+	/// Multiplier applied to a summoned shadow's health and damage.
+	pub fn stat_multiplier(&self) -> f32 {
+		match self {
+			ShadowRank::Normal => 1.0,
+			ShadowRank::Elite => 1.25,
+			ShadowRank::Knight => 1.5,
+			ShadowRank::General => 2.0,
+			ShadowRank::Marshal => 3.0,
+		}
+	}
+}
+
+/// Describes which shadow is extracted from a defeated enemy.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ShadowExtractionData {
+	/// Enemy type the shadow is extracted from.
+	pub enemy: EnemyMobType,
+	/// Whether this entry applies to the boss variant of the enemy.
+	/// Boss extractions are unique, named shadows (e.g. Igris, Beru).
+	pub boss: bool,
+	/// Allied mob type the shadow is summoned as.
+	pub shadow: AllyMobType,
+	/// Display name of the shadow.
+	pub name: String,
+	/// Rank of the shadow.
+	pub rank: ShadowRank,
+}
+
+/// Extraction table loaded from `shadow_extractions.ron`.
+#[derive(Resource, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ShadowExtractionsResource {
+	pub extractions: Vec<ShadowExtractionData>,
+}
+
+impl ShadowExtractionsResource {
+	/// Returns the extraction entry of a defeated enemy, if it can be extracted.
+	pub fn get(
+		&self,
+		enemy: EnemyMobType,
+		boss: bool,
+	) -> Option<&ShadowExtractionData> {
+		self.extractions.iter().find(|extraction| {
+			extraction.enemy == enemy && extraction.boss == boss
+		})
+	}
+}
+
+/// A shadow type that has been extracted at least once.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub struct ShadowRecord {
+	/// Allied mob type the shadow is summoned as.
+	pub mob_type: AllyMobType,
+	/// Display name of the shadow.
+	pub name: String,
+	/// Rank of the shadow.
+	pub rank: ShadowRank,
+	/// Named shadows exist only once in the army.
+	pub unique: bool,
+	/// How many shadows of this type the army holds.
+	pub count: u32,
+}
+
+/// The player's shadow army. Persists between runs.
+#[derive(Resource, Deserialize, Serialize, Clone, Debug, Default, PartialEq)]
+pub struct ShadowArmyResource {
+	/// Every extracted shadow type.
+	pub shadows: Vec<ShadowRecord>,
+	/// Shadow types `ShadowSummon` raises first, in order of preference.
+	pub preferred: Vec<AllyMobType>,
+}
+
+impl ShadowArmyResource {
+	/// Returns the record of a shadow type.
+	pub fn get(&self, mob_type: AllyMobType) -> Option<&ShadowRecord> {
+		self.shadows
+			.iter()
+			.find(|shadow| shadow.mob_type == mob_type)
+	}
+
+	/// Adds an extracted shadow to the army.
+	/// Returns `false` if it is a named shadow the army already has.
+	pub fn extract(&mut self, extraction: &ShadowExtractionData) -> bool {
+		if let Some(record) = self
+			.shadows
+			.iter_mut()
+			.find(|shadow| shadow.mob_type == extraction.shadow)
+		{
+			if record.unique {
+				return false;
+			}
+			record.count += 1;
+			return true;
+		}
+
+		self.shadows.push(ShadowRecord {
+			mob_type: extraction.shadow,
+			name: extraction.name.clone(),
+			rank: extraction.rank,
+			unique: extraction.boss,
+			count: 1,
+		});
+		true
+	}
+
+	/// Adds or removes a shadow type from the preferred list.
+	/// Only extracted shadows can be preferred.
+	pub fn toggle_preferred(&mut self, mob_type: AllyMobType) {
+		if let Some(index) = self.preferred.iter().position(|&t| t == mob_type) {
+			self.preferred.remove(index);
+		} else if self.get(mob_type).is_some() {
+			self.preferred.push(mob_type);
+		}
+	}
+
+	/// Shadow types the next press of `CycleShadowPreference` toggles.
+	///
+	/// Each press makes the extracted shadow after the last preferred one the
+	/// only preferred shadow. Pressing past the last shadow clears the
+	/// preference again.
+	pub fn next_preference_toggles(&self) -> Vec<AllyMobType> {
+		let next = match self.preferred.last() {
+			Some(current) => self
+				.shadows
+				.iter()
+				.position(|shadow| shadow.mob_type == *current)
+				.and_then(|index| self.shadows.get(index + 1)),
+			None => self.shadows.first(),
+		};
+		let mut toggles = self.preferred.clone();
+		toggles.extend(next.map(|shadow| shadow.mob_type));
+		toggles
+	}
+
+	/// Shadow types a `ShadowSummon` cast should raise, in order.
+	///
+	/// Preferred shadows come first (each up to the number the army holds),
+	/// the remaining slots are filled with generic `ShadowSummon` shadows.
+	pub fn summon_order(&self, count: u32) -> Vec<AllyMobType> {
+		let mut order = Vec::with_capacity(count as usize);
+		for record in self
+			.preferred
+			.iter()
+			.filter_map(|&mob_type| self.get(mob_type))
+		{
+			for _ in 0..record.count {
+				if order.len() as u32 >= count {
+					return order;
+				}
+				order.push(record.mob_type);
+			}
+		}
+		order.resize(count as usize, AllyMobType::ShadowSummon);
+		order
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn extraction(shadow: AllyMobType, boss: bool) -> ShadowExtractionData {
+		ShadowExtractionData {
+			enemy: EnemyMobType::Knight,
+			boss,
+			shadow,
+			name: format!("{shadow:?}"),
+			rank: ShadowRank::Knight,
+		}
+	}
+
+	/// An army holding one Igris and two shadow knights.
+	fn army() -> ShadowArmyResource {
+		let mut army = ShadowArmyResource::default();
+		army.extract(&extraction(AllyMobType::Igris, true));
+		army.extract(&extraction(AllyMobType::ShadowKnight, false));
+		army.extract(&extraction(AllyMobType::ShadowKnight, false));
+		army
+	}
+
+	#[test]
+	fn only_extracted_shadows_can_be_preferred() {
+		let mut army = army();
+
+		army.toggle_preferred(AllyMobType::Beru);
+		assert!(army.preferred.is_empty());
+
+		army.toggle_preferred(AllyMobType::ShadowKnight);
+		army.toggle_preferred(AllyMobType::Igris);
+		assert_eq!(
+			army.preferred,
+			[AllyMobType::ShadowKnight, AllyMobType::Igris]
+		);
+
+		army.toggle_preferred(AllyMobType::ShadowKnight);
+		assert_eq!(army.preferred, [AllyMobType::Igris]);
+	}
+
+	#[test]
+	fn summon_order_raises_preferred_shadows_first() {
+		let mut army = army();
+		assert_eq!(army.summon_order(2), [AllyMobType::ShadowSummon; 2]);
+
+		army.toggle_preferred(AllyMobType::ShadowKnight);
+		army.toggle_preferred(AllyMobType::Igris);
+		// Each preferred type is raised at most as often as the army holds it
+		assert_eq!(
+			army.summon_order(4),
+			[
+				AllyMobType::ShadowKnight,
+				AllyMobType::ShadowKnight,
+				AllyMobType::Igris,
+				AllyMobType::ShadowSummon,
+			]
+		);
+		assert_eq!(army.summon_order(1), [AllyMobType::ShadowKnight]);
+	}
+
+	#[test]
+	fn preference_toggles_cycle_through_the_army() {
+		let mut army = army();
+		let mut preferred = vec![];
+		for _ in 0..3 {
+			for mob_type in army.next_preference_toggles() {
+				army.toggle_preferred(mob_type);
+			}
+			preferred.push(army.preferred.clone());
+		}
+
+		assert_eq!(
+			preferred,
+			[
+				vec![AllyMobType::Igris],
+				vec![AllyMobType::ShadowKnight],
+				vec![],
+			]
+		);
+		assert!(ShadowArmyResource::default()
+			.next_preference_toggles()
+			.is_empty());
+	}
+}
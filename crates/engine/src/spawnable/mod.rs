@@ -31,7 +31,24 @@ pub enum SpawnableType {
 #[derive(Deserialize, Debug, Hash, PartialEq, Eq, Clone, Display, Copy)]
 pub enum Faction {
 	Ally,
-	// Enemy,
+	Enemy,
+}
+
+impl Faction {
+	/// Faction of a target, given whether it is a hostile mob. Players and
+	/// their summons are allies.
+	pub fn of(is_enemy: bool) -> Self {
+		if is_enemy {
+			Faction::Enemy
+		} else {
+			Faction::Ally
+		}
+	}
+
+	/// Returns `true` if attacks of this faction hurt entities of `other`.
+	pub fn is_hostile_to(self, other: Faction) -> bool {
+		self != other
+	}
 }
 
 
@@ -0,0 +1,240 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use bevy::prelude::*;
+use serde::Deserialize;
+
+/// Unique identifier of a projectile kind (e.g. `"Bullet"`).
+/// `projectiles.ron` is keyed by it.
+#[derive(Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
+#[serde(transparent)]
+pub struct ProjectileId(pub String);
+
+impl ProjectileId {
+	pub fn new(id: impl Into<String>) -> Self {
+		Self(id.into())
+	}
+}
+
+impl fmt::Display for ProjectileId {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(&self.0)
+	}
+}
+
+/// Behaviors of a projectile kind. Every behavior is optional and they can be
+/// combined freely, e.g. a homing projectile that also pierces.
+#[derive(Deserialize, Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct ProjectileData {
+	/// Extra targets the projectile passes through before despawning.
+	#[serde(default)]
+	pub pierce: u32,
+	#[serde(default)]
+	pub homing: Option<HomingData>,
+	/// Times the projectile bounces off the arena bounds.
+	#[serde(default)]
+	pub bounces: u32,
+	#[serde(default)]
+	pub chain: Option<ChainData>,
+}
+
+impl ProjectileData {
+	/// Inserts the components of every behavior of this kind.
+	pub fn insert_behaviors(&self, entity_commands: &mut EntityCommands) {
+		if self.pierce > 0 {
+			entity_commands.insert(PierceComponent {
+				remaining: self.pierce,
+			});
+		}
+		if let Some(homing) = &self.homing {
+			entity_commands.insert(HomingComponent::from(homing));
+		}
+		if self.bounces > 0 {
+			entity_commands.insert(BounceComponent {
+				remaining: self.bounces,
+			});
+		}
+		if let Some(chain) = &self.chain {
+			entity_commands.insert(ChainComponent::from(chain));
+		}
+	}
+}
+
+/// Steering of a homing projectile.
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct HomingData {
+	/// Maximum turn (in radians per second).
+	pub turn_rate: f32,
+	/// Distance within which enemies are tracked.
+	pub range: f32,
+}
+
+/// Chaining of a projectile from one target to the next.
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ChainData {
+	/// Extra targets the projectile jumps to after its first hit.
+	pub count: u32,
+	/// Distance from the last target within which the next one is picked.
+	pub range: f32,
+	/// Damage multiplier applied on every jump (e.g. `0.75` = -25% per jump).
+	pub falloff: f32,
+}
+
+/// Projectile kinds keyed by id, loaded from `projectiles.ron`.
+#[derive(Resource, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProjectilesResource {
+	pub projectiles: HashMap<ProjectileId, ProjectileData>,
+}
+
+impl ProjectilesResource {
+	pub fn get(&self, id: &ProjectileId) -> Option<&ProjectileData> {
+		self.projectiles.get(id)
+	}
+}
+
+/// Lets a projectile pass through `remaining` more targets.
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct PierceComponent {
+	pub remaining: u32,
+}
+
+/// Turns a projectile toward the nearest enemy within `range`.
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct HomingComponent {
+	pub turn_rate: f32,
+	pub range: f32,
+}
+
+impl From<&HomingData> for HomingComponent {
+	fn from(data: &HomingData) -> Self {
+		Self {
+			turn_rate: data.turn_rate,
+			range: data.range,
+		}
+	}
+}
+
+/// Bounces a projectile off the arena bounds `remaining` more times.
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct BounceComponent {
+	pub remaining: u32,
+}
+
+/// Makes a projectile jump to `remaining` more targets after a hit.
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct ChainComponent {
+	pub remaining: u32,
+	pub range: f32,
+	pub falloff: f32,
+}
+
+impl From<&ChainData> for ChainComponent {
+	fn from(data: &ChainData) -> Self {
+		Self {
+			remaining: data.count,
+			range: data.range,
+			falloff: data.falloff,
+		}
+	}
+}
+
+/// Makes a projectile circle around `center` instead of flying along its
+/// velocity. The projectile despawns with its center.
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct OrbitComponent {
+	pub center: Entity,
+	/// Distance between the projectile and its center.
+	pub radius: f32,
+	/// Radians per second the projectile turns around its center.
+	pub angular_speed: f32,
+	/// Current angle (in radians) of the projectile around its center.
+	pub angle: f32,
+}
+
+impl OrbitComponent {
+	/// Advances the orbit by `delta` seconds and returns the projectile's
+	/// offset from its center.
+	pub fn advance(&mut self, delta: f32) -> Vec2 {
+		self.angle = (self.angle + self.angular_speed * delta)
+			.rem_euclid(std::f32::consts::TAU);
+		Vec2::from_angle(self.angle) * self.radius
+	}
+}
+
+/// Rotates `velocity` toward `direction` by at most `max_angle` radians,
+/// keeping its speed.
+pub fn steer(velocity: Vec2, direction: Vec2, max_angle: f32) -> Vec2 {
+	let angle = velocity.angle_to(direction);
+	if !angle.is_finite() {
+		return velocity;
+	}
+	Vec2::from_angle(angle.clamp(-max_angle, max_angle)).rotate(velocity)
+}
+
+/// Reflects a projectile at `position` moving by `velocity` off the bounds
+/// `min`..`max`. Returns the new velocity if it bounced.
+pub fn bounce(
+	position: Vec2,
+	velocity: Vec2,
+	min: Vec2,
+	max: Vec2,
+) -> Option<Vec2> {
+	let mut reflected = velocity;
+	if (position.x <= min.x && velocity.x < 0.0)
+		|| (position.x >= max.x && velocity.x > 0.0)
+	{
+		reflected.x = -reflected.x;
+	}
+	if (position.y <= min.y && velocity.y < 0.0)
+		|| (position.y >= max.y && velocity.y > 0.0)
+	{
+		reflected.y = -reflected.y;
+	}
+	(reflected != velocity).then_some(reflected)
+}
+
+#[cfg(test)]
+mod tests {
+	use std::f32::consts::FRAC_PI_2;
+
+	use super::*;
+
+	#[test]
+	fn steer_is_capped_by_max_angle() {
+		let velocity = steer(Vec2::new(100.0, 0.0), Vec2::Y, 0.1);
+
+		assert!((velocity.length() - 100.0).abs() < 1e-3);
+		assert!((velocity.to_angle() - 0.1).abs() < 1e-5);
+	}
+
+	#[test]
+	fn steer_reaches_close_directions() {
+		let direction = Vec2::from_angle(0.05);
+		let velocity = steer(Vec2::X * 10.0, direction, FRAC_PI_2);
+
+		assert!((velocity.normalize() - direction).length() < 1e-5);
+	}
+
+	#[test]
+	fn bounce_reflects_only_outgoing_axes() {
+		let min = Vec2::splat(-100.0);
+		let max = Vec2::splat(100.0);
+
+		assert_eq!(
+			bounce(Vec2::new(100.0, 0.0), Vec2::new(5.0, 3.0), min, max),
+			Some(Vec2::new(-5.0, 3.0))
+		);
+		assert_eq!(
+			bounce(Vec2::new(100.0, 0.0), Vec2::new(-5.0, 3.0), min, max),
+			None
+		);
+		assert_eq!(
+			bounce(Vec2::new(-100.0, 100.0), Vec2::new(-5.0, 3.0), min, max),
+			Some(Vec2::new(5.0, -3.0))
+		);
+	}
+}
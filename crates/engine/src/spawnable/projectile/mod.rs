@@ -1,3 +1,8 @@
+pub mod behavior;
+
+use std::collections::HashSet;
+
+use crate::combat::{CriticalStats, DamageType};
 use crate::spawnable::Faction;
 use bevy::prelude::*;
 use serde::Deserialize;
@@ -19,5 +24,71 @@ impl ProjectileType {
 	}
 }
 
-#[derive(Component)]
-pub struct ProjectileComponent;
\ No newline at end of file
+/// Radius (in world units) within which a projectile of size 1.0 hits a target.
+pub const PROJECTILE_HIT_RADIUS: f32 = 16.0;
+
+/// A projectile in flight. It moves by `velocity`, hits the first hostile
+/// target within `radius` and despawns once `lifetime` runs out.
+#[derive(Component, Debug)]
+pub struct ProjectileComponent {
+	pub projectile_type: ProjectileType,
+	/// Entity that fired the projectile.
+	pub owner: Entity,
+	pub damage: u32,
+	pub damage_type: DamageType,
+	pub critical: CriticalStats,
+	/// Velocity in units per second.
+	pub velocity: Vec2,
+	pub radius: f32,
+	/// Remaining time before the projectile despawns.
+	pub lifetime: Timer,
+	/// Entities already hit, never hit twice by a piercing or chaining projectile.
+	pub hit: HashSet<Entity>,
+}
+
+/// Returns the directions of the `count` projectiles of one shot.
+///
+/// Neighbouring projectiles are `gap / count` radians apart, so the spread
+/// tightens as the count grows, and the whole spread never exceeds `max_arc`.
+/// The spread is centered on `direction`.
+pub fn spread_directions(
+	direction: Vec2,
+	count: u32,
+	gap: f32,
+	max_arc: f32,
+) -> Vec<Vec2> {
+	if count <= 1 {
+		return vec![direction; count as usize];
+	}
+
+	let step = (gap / count as f32).min(max_arc / (count - 1) as f32);
+	let start = -step * (count - 1) as f32 * 0.5;
+	(0..count)
+		.map(|i| Vec2::from_angle(start + step * i as f32).rotate(direction))
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn single_projectile_keeps_its_direction() {
+		assert_eq!(
+			spread_directions(Vec2::X, 1, 1.0, 1.0),
+			vec![Vec2::X]
+		);
+		assert!(spread_directions(Vec2::X, 0, 1.0, 1.0).is_empty());
+	}
+
+	#[test]
+	fn spread_is_symmetric_and_capped() {
+		let directions = spread_directions(Vec2::X, 5, std::f32::consts::PI, 1.0);
+
+		assert_eq!(directions.len(), 5);
+		assert!((directions[2] - Vec2::X).length() < 1e-5);
+		let arc = directions[0].angle_to(directions[4]).abs();
+		assert!((arc - 1.0).abs() < 1e-5);
+		assert!((directions[0].y + directions[4].y).abs() < 1e-5);
+	}
+}
\ No newline at end of file
@@ -1,5 +1,6 @@
 use bevy_ecs_macros::Component;
 use serde::Deserialize;
+use strum_macros::EnumIter;
 
 /// Player States
 #[derive(
@@ -11,18 +12,25 @@ use serde::Deserialize;
     Default,
     Hash,
     Eq,
-    Deserialize
+    Deserialize,
+    EnumIter
 )]
 pub enum PlayerState {
     #[default]
     Idle,
     Running,
     LightAttack,
+    /// Holding the heavy attack to charge it.
+    Charging,
+    /// Releasing a charged heavy attack.
+    HeavyAttack,
 }
 
 impl PlayerState {
     pub fn priority(&self) -> u8 {
         match self {
+            PlayerState::HeavyAttack => 5,
+            PlayerState::Charging => 4,
             PlayerState::LightAttack => 3,
             PlayerState::Running => 2,
             PlayerState::Idle => 1,
@@ -0,0 +1,410 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::time::Duration;
+
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::combat::DamageType;
+
+/// Unique identifier of a status effect (e.g. `"Chill"`).
+/// `status_effects.ron` is keyed by it.
+#[derive(Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
+#[serde(transparent)]
+pub struct StatusEffectId(pub String);
+
+impl StatusEffectId {
+    pub fn new(id: impl Into<String>) -> Self {
+        Self(id.into())
+    }
+}
+
+impl fmt::Display for StatusEffectId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// What a status effect does to the entity it is applied to.
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StatusEffectKind {
+    /// Changes movement speed by `magnitude` per stack
+    /// (e.g. `-0.3` = 30% slower, `0.2` = 20% faster).
+    MovementSpeed,
+    /// Deals `magnitude` damage per stack on every tick.
+    DamageOverTime,
+    /// Stops the entity from moving and attacking.
+    Stun,
+    /// Makes the entity run away from the effect's source.
+    Fear,
+}
+
+/// How a status effect behaves when applied to an entity that already has it.
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum StackingRule {
+    /// Restarts the duration of the existing effect.
+    #[default]
+    Refresh,
+    /// Adds a stack (up to `max_stacks`) and restarts the duration.
+    Stack,
+    /// Keeps the existing effect untouched.
+    Ignore,
+    /// Adds a separate instance with its own duration, up to `max_stacks`
+    /// instances. At the limit, the instance closest to expiring restarts.
+    Independent,
+}
+
+/// Data definition of a status effect, loaded from `status_effects.ron`.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct StatusEffectData {
+    pub kind: StatusEffectKind,
+    /// Seconds the effect lasts.
+    pub duration: f32,
+    /// Seconds between two ticks. Only used by effects that tick.
+    #[serde(default)]
+    pub tick_interval: Option<f32>,
+    /// Strength of the effect, see [`StatusEffectKind`].
+    /// Unused by stuns and fears.
+    #[serde(default)]
+    pub magnitude: f32,
+    /// Type of the damage dealt by damage over time effects.
+    #[serde(default)]
+    pub damage_type: DamageType,
+    #[serde(default)]
+    pub stacking: StackingRule,
+    /// Maximum number of stacks when `stacking` is `Stack`, or of
+    /// instances when it is `Independent`.
+    #[serde(default = "default_max_stacks")]
+    pub max_stacks: u32,
+}
+
+fn default_max_stacks() -> u32 {
+    1
+}
+
+/// Every status effect in the game, keyed by id.
+#[derive(Resource, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StatusEffectsResource {
+    pub effects: HashMap<StatusEffectId, StatusEffectData>,
+}
+
+/// Requests a status effect to be applied to `target`.
+/// Sent by abilities or by the damage pipeline for hits that carry an effect.
+#[derive(Event, Clone, Debug)]
+pub struct ApplyStatusEffectEvent {
+    pub target: Entity,
+    pub effect: StatusEffectId,
+    /// Entity responsible for the effect, credited for its damage.
+    pub source: Option<Entity>,
+}
+
+/// A status effect currently affecting an entity.
+#[derive(Clone, Debug)]
+pub struct ActiveStatusEffect {
+    pub id: StatusEffectId,
+    pub kind: StatusEffectKind,
+    pub magnitude: f32,
+    pub damage_type: DamageType,
+    pub stacks: u32,
+    pub max_stacks: u32,
+    pub stacking: StackingRule,
+    /// Remaining duration of the effect.
+    pub timer: Timer,
+    /// Tick timer for effects that act periodically.
+    pub tick_timer: Option<Timer>,
+    pub source: Option<Entity>,
+}
+
+impl ActiveStatusEffect {
+    fn new(
+        id: StatusEffectId,
+        data: &StatusEffectData,
+        source: Option<Entity>,
+    ) -> Self {
+        Self {
+            id,
+            kind: data.kind,
+            magnitude: data.magnitude,
+            damage_type: data.damage_type,
+            stacks: 1,
+            max_stacks: data.max_stacks.max(1),
+            stacking: data.stacking,
+            timer: Timer::from_seconds(data.duration, TimerMode::Once),
+            tick_timer: data
+                .tick_interval
+                .map(|secs| Timer::from_seconds(secs, TimerMode::Repeating)),
+            source,
+        }
+    }
+
+    /// Total strength of the effect over all stacks.
+    pub fn total_magnitude(&self) -> f32 {
+        self.magnitude * self.stacks as f32
+    }
+
+    /// Fraction of the duration left (1.0 to 0.0), e.g. for UI icons.
+    pub fn remaining_fraction(&self) -> f32 {
+        1.0 - self.timer.fraction()
+    }
+}
+
+/// Damage due from a damage over time effect on a tick.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StatusEffectTick {
+    pub damage: u32,
+    pub damage_type: DamageType,
+    pub source: Option<Entity>,
+}
+
+/// Status effects active on an entity.
+/// Entities without this component get it on the first applied effect.
+#[derive(Component, Clone, Debug, Default)]
+pub struct StatusEffectsComponent {
+    effects: Vec<ActiveStatusEffect>,
+}
+
+impl StatusEffectsComponent {
+    /// Active effects, for UI icons and other queries.
+    pub fn iter(&self) -> impl Iterator<Item = &ActiveStatusEffect> {
+        self.effects.iter()
+    }
+
+    /// Returns the active effect with the given id.
+    pub fn get(&self, id: &StatusEffectId) -> Option<&ActiveStatusEffect> {
+        self.effects.iter().find(|effect| &effect.id == id)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.effects.is_empty()
+    }
+
+    /// Applies an effect following its stacking rule.
+    pub fn apply(
+        &mut self,
+        id: &StatusEffectId,
+        data: &StatusEffectData,
+        source: Option<Entity>,
+    ) {
+        if data.stacking == StackingRule::Independent {
+            self.apply_independent(id, data, source);
+            return;
+        }
+
+        let Some(existing) =
+            self.effects.iter_mut().find(|effect| &effect.id == id)
+        else {
+            self.effects.push(ActiveStatusEffect::new(
+                id.clone(),
+                data,
+                source,
+            ));
+            return;
+        };
+
+        match existing.stacking {
+            StackingRule::Refresh => existing.timer.reset(),
+            StackingRule::Stack => {
+                existing.stacks =
+                    (existing.stacks + 1).min(existing.max_stacks);
+                existing.timer.reset();
+            },
+            StackingRule::Ignore | StackingRule::Independent => return,
+        }
+        existing.source = source.or(existing.source);
+    }
+
+    /// Adds an instance of an `Independent` effect, or restarts the one
+    /// closest to expiring once `max_stacks` instances are active.
+    fn apply_independent(
+        &mut self,
+        id: &StatusEffectId,
+        data: &StatusEffectData,
+        source: Option<Entity>,
+    ) {
+        let mut instances = self
+            .effects
+            .iter_mut()
+            .filter(|effect| &effect.id == id)
+            .collect::<Vec<_>>();
+        if instances.len() < data.max_stacks.max(1) as usize {
+            self.effects.push(ActiveStatusEffect::new(
+                id.clone(),
+                data,
+                source,
+            ));
+            return;
+        }
+
+        instances.sort_by_key(|effect| effect.timer.remaining());
+        let oldest = &mut instances[0];
+        oldest.timer.reset();
+        oldest.source = source.or(oldest.source);
+    }
+
+    /// Removes every active effect.
+    pub fn clear(&mut self) {
+        self.effects.clear();
+    }
+
+    /// Multiplier applied to the entity's movement speed.
+    /// Never drops below zero.
+    pub fn movement_multiplier(&self) -> f32 {
+        self.effects
+            .iter()
+            .filter(|effect| effect.kind == StatusEffectKind::MovementSpeed)
+            .map(|effect| 1.0 + effect.total_magnitude())
+            .product::<f32>()
+            .max(0.0)
+    }
+
+    /// Returns `true` while a stun stops the entity from moving and
+    /// attacking.
+    pub fn is_stunned(&self) -> bool {
+        self.effects
+            .iter()
+            .any(|effect| effect.kind == StatusEffectKind::Stun)
+    }
+
+    /// Entity the entity runs away from, if it is feared. The most recently
+    /// applied fear wins.
+    pub fn fear_source(&self) -> Option<Entity> {
+        self.effects
+            .iter()
+            .rev()
+            .filter(|effect| effect.kind == StatusEffectKind::Fear)
+            .find_map(|effect| effect.source)
+    }
+
+    /// Advances all effects, removes expired ones and returns the damage
+    /// over time ticks that happened.
+    pub fn tick(&mut self, delta: Duration) -> Vec<StatusEffectTick> {
+        let mut ticks = Vec::new();
+        for effect in self.effects.iter_mut() {
+            effect.timer.tick(delta);
+            if effect.kind != StatusEffectKind::DamageOverTime {
+                continue;
+            }
+            let Some(tick_timer) = effect.tick_timer.as_mut() else {
+                continue;
+            };
+            tick_timer.tick(delta);
+            let damage = (effect.magnitude * effect.stacks as f32).round()
+                as u32
+                * tick_timer.times_finished_this_tick();
+            if damage > 0 {
+                ticks.push(StatusEffectTick {
+                    damage,
+                    damage_type: effect.damage_type,
+                    source: effect.source,
+                });
+            }
+        }
+        self.effects.retain(|effect| !effect.timer.finished());
+        ticks
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn poison(stacking: StackingRule, max_stacks: u32) -> StatusEffectData {
+        StatusEffectData {
+            kind: StatusEffectKind::DamageOverTime,
+            duration: 2.0,
+            tick_interval: Some(1.0),
+            magnitude: 1.0,
+            damage_type: DamageType::Physical,
+            stacking,
+            max_stacks,
+        }
+    }
+
+    fn tick_damage(effects: &mut StatusEffectsComponent, secs: f32) -> u32 {
+        effects
+            .tick(Duration::from_secs_f32(secs))
+            .iter()
+            .map(|tick| tick.damage)
+            .sum()
+    }
+
+    #[test]
+    fn refresh_restarts_the_duration() {
+        let id = StatusEffectId::new("Poison");
+        let data = poison(StackingRule::Refresh, 1);
+        let mut effects = StatusEffectsComponent::default();
+
+        effects.apply(&id, &data, None);
+        tick_damage(&mut effects, 1.5);
+        effects.apply(&id, &data, None);
+        tick_damage(&mut effects, 1.5);
+
+        let effect = effects.get(&id).unwrap();
+        assert_eq!(effect.stacks, 1);
+        assert!(effect.remaining_fraction() > 0.0);
+    }
+
+    #[test]
+    fn stacks_are_capped_at_max_stacks() {
+        let id = StatusEffectId::new("Poison");
+        let data = poison(StackingRule::Stack, 3);
+        let mut effects = StatusEffectsComponent::default();
+
+        for _ in 0..5 {
+            effects.apply(&id, &data, None);
+        }
+
+        assert_eq!(effects.get(&id).unwrap().stacks, 3);
+        assert_eq!(tick_damage(&mut effects, 1.0), 3);
+    }
+
+    #[test]
+    fn independent_instances_expire_separately() {
+        let id = StatusEffectId::new("Poison");
+        let data = poison(StackingRule::Independent, 2);
+        let mut effects = StatusEffectsComponent::default();
+
+        effects.apply(&id, &data, None);
+        assert_eq!(tick_damage(&mut effects, 1.0), 1);
+        effects.apply(&id, &data, None);
+        assert_eq!(effects.iter().count(), 2);
+        assert_eq!(tick_damage(&mut effects, 1.0), 2);
+        assert_eq!(effects.iter().count(), 1);
+
+        effects.apply(&id, &data, None);
+        effects.apply(&id, &data, None);
+        assert_eq!(effects.iter().count(), 2);
+    }
+
+    #[test]
+    fn stun_and_fear_are_reported() {
+        let source = Entity::from_raw(7);
+        let mut effects = StatusEffectsComponent::default();
+        let fear = StatusEffectData {
+            kind: StatusEffectKind::Fear,
+            tick_interval: None,
+            ..poison(StackingRule::Refresh, 1)
+        };
+        let stun = StatusEffectData {
+            kind: StatusEffectKind::Stun,
+            ..fear.clone()
+        };
+
+        effects.apply(
+            &StatusEffectId::new("Fear"),
+            &fear,
+            Some(source),
+        );
+        assert!(!effects.is_stunned());
+        assert_eq!(effects.fear_source(), Some(source));
+
+        effects.apply(
+            &StatusEffectId::new("Stun"),
+            &stun,
+            None,
+        );
+        assert!(effects.is_stunned());
+    }
+}
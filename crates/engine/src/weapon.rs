@@ -1,4 +1,432 @@
-use bevy_ecs_macros::Component;
+use std::collections::HashMap;
+use std::f32::consts::PI;
+use std::fmt;
 
-#[derive(Component)]
-pub struct WeaponComponent;
\ No newline at end of file
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::combat::DamageType;
+use crate::passive::{PassiveId, PassiveInventoryComponent};
+use crate::spawnable::projectile::behavior::{steer, ProjectileId};
+
+/// Unique identifier of a weapon (e.g. `"MagicWand"`).
+/// `weapons.ron` is keyed by it.
+#[derive(Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
+#[serde(transparent)]
+pub struct WeaponId(pub String);
+
+impl WeaponId {
+    pub fn new(id: impl Into<String>) -> Self {
+        Self(id.into())
+    }
+}
+
+impl fmt::Display for WeaponId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Sprite of a weapon, looked up in `WeaponAssets`.
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum WeaponSprite {
+    IceStaff,
+}
+
+/// How an auto-firing weapon picks the direction of its shots.
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum WeaponTargeting {
+    /// Towards the nearest living enemy within `range`. The weapon holds its
+    /// fire while no enemy is in range.
+    Nearest,
+    /// Along the player's `AimComponent`.
+    Facing,
+    /// In a random direction.
+    Random,
+    /// Projectiles circle around the player instead of flying away.
+    Orbit {
+        /// Distance between the player and the orbiting projectiles.
+        radius: f32,
+        /// Radians per second the projectiles turn around the player.
+        angular_speed: f32,
+    },
+}
+
+/// Definition of a weapon, either held by a player or firing on its own
+/// from the player's inventory.
+///
+/// The weapon's shots scale with the owner's `PlayerOutgoingDamageComponent`:
+/// the owner's weapon damage is multiplied by `damage_multiplier` and added
+/// to the weapon's, projectile speed is multiplied from the owner's, and the
+/// owner's extra projectiles are added to every shot.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct WeaponData {
+    /// Display name of the weapon.
+    pub name: String,
+    pub sprite: WeaponSprite,
+    /// Scale of the sprite while the weapon is held.
+    #[serde(default = "default_multiplier")]
+    pub scale: f32,
+    /// Point of the owner the weapon turns around while held, in the owner's
+    /// local space.
+    #[serde(default)]
+    pub pivot: Vec2,
+    /// Distance between the pivot and the weapon while held, in the owner's
+    /// local space, so it grows with the owner's sprite.
+    #[serde(default)]
+    pub hold_radius: f32,
+    /// Radians per second the weapon turns towards the aimed direction
+    /// while held.
+    #[serde(default = "default_turn_rate")]
+    pub turn_rate: f32,
+    /// Damage of a hit, added to the owner's scaled weapon damage.
+    pub damage: u32,
+    /// Multiplier applied to the owner's weapon damage.
+    #[serde(default = "default_multiplier")]
+    pub damage_multiplier: f32,
+    #[serde(default)]
+    pub damage_type: DamageType,
+    /// Shots per second.
+    pub fire_rate: f32,
+    /// Kind of projectile fired, defined in `projectiles.ron`.
+    pub projectile: ProjectileId,
+    /// How the weapon aims when it fires on its own from the inventory.
+    pub targeting: WeaponTargeting,
+    /// Multiplier applied to the owner's projectile speed.
+    #[serde(default = "default_multiplier")]
+    pub speed_multiplier: f32,
+    /// Projectiles fired per shot, before the owner's extra projectiles.
+    #[serde(default = "default_projectile_count")]
+    pub projectile_count: u32,
+    /// Distance within which `Nearest` targeting picks an enemy.
+    #[serde(default = "default_range")]
+    pub range: f32,
+    /// Highest level the weapon can reach in the inventory.
+    #[serde(default = "default_max_level")]
+    pub max_level: u32,
+}
+
+fn default_multiplier() -> f32 {
+    1.0
+}
+
+fn default_turn_rate() -> f32 {
+    4.0 * PI
+}
+
+fn default_projectile_count() -> u32 {
+    1
+}
+
+fn default_range() -> f32 {
+    600.0
+}
+
+fn default_max_level() -> u32 {
+    1
+}
+
+/// Every auto-firing weapon, loaded from `weapons.ron`.
+#[derive(Resource, Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct WeaponsResource {
+    pub weapons: HashMap<WeaponId, WeaponData>,
+}
+
+impl WeaponData {
+    /// Seconds between two shots.
+    pub fn cooldown(&self) -> f32 {
+        1.0 / self.fire_rate.max(f32::EPSILON)
+    }
+
+    /// Damage of a hit of the weapon held by an owner with `weapon_damage`.
+    pub fn hit_damage(&self, weapon_damage: u32) -> u32 {
+        (weapon_damage as f32 * self.damage_multiplier).round() as u32
+            + self.damage
+    }
+}
+
+impl WeaponsResource {
+    pub fn get(&self, id: &WeaponId) -> Option<&WeaponData> {
+        self.weapons.get(id)
+    }
+}
+
+/// Auto-firing weapon held in a player's inventory.
+#[derive(Clone, Debug)]
+pub struct EquippedWeapon {
+    pub id: WeaponId,
+    pub data: WeaponData,
+    /// Level of the weapon, from 1 to `data.max_level`.
+    pub level: u32,
+    /// Time left before the next shot.
+    pub cooldown: Timer,
+}
+
+impl EquippedWeapon {
+    pub fn new(id: WeaponId, data: WeaponData) -> Self {
+        let cooldown = Timer::from_seconds(data.cooldown(), TimerMode::Once);
+        Self {
+            id,
+            data,
+            level: 1,
+            cooldown,
+        }
+    }
+
+    /// Returns `true` once the weapon reached its highest level.
+    pub fn is_max_level(&self) -> bool {
+        self.level >= self.data.max_level
+    }
+}
+
+/// Auto-firing weapons of a player, each firing on its own cooldown.
+#[derive(Component, Clone, Debug, Default)]
+pub struct WeaponInventoryComponent {
+    pub weapons: Vec<EquippedWeapon>,
+}
+
+impl WeaponInventoryComponent {
+    /// Returns `true` if the inventory holds the weapon `id`.
+    pub fn contains(&self, id: &WeaponId) -> bool {
+        self.weapons.iter().any(|weapon| weapon.id == *id)
+    }
+
+    /// Adds a weapon, unless it is already held or the inventory already
+    /// holds `max_weapons`. Returns `true` if the weapon was added.
+    pub fn equip(
+        &mut self,
+        id: WeaponId,
+        data: WeaponData,
+        max_weapons: usize,
+    ) -> bool {
+        if self.contains(&id) || self.weapons.len() >= max_weapons {
+            return false;
+        }
+
+        self.weapons.push(EquippedWeapon::new(id, data));
+        true
+    }
+
+    /// Raises the level of the weapon `id` by one, up to its `max_level`.
+    /// Returns `true` if the level changed.
+    pub fn level_up(&mut self, id: &WeaponId) -> bool {
+        let Some(weapon) = self.weapons.iter_mut().find(|w| w.id == *id) else {
+            return false;
+        };
+        if weapon.is_max_level() {
+            return false;
+        }
+        weapon.level += 1;
+        true
+    }
+
+    /// Replaces the weapon `id` by `evolved`, keeping its slot.
+    /// Returns `true` if the weapon was held.
+    pub fn evolve(
+        &mut self,
+        id: &WeaponId,
+        evolved: WeaponId,
+        data: WeaponData,
+    ) -> bool {
+        let Some(weapon) = self.weapons.iter_mut().find(|w| w.id == *id) else {
+            return false;
+        };
+        *weapon = EquippedWeapon::new(evolved, data);
+        true
+    }
+}
+
+/// Recipe evolving `weapon` into `evolved` once the weapon is at its highest
+/// level and the player holds `passive` at its highest level.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct EvolutionRecipe {
+    pub weapon: WeaponId,
+    pub passive: PassiveId,
+    pub evolved: WeaponId,
+}
+
+impl EvolutionRecipe {
+    /// Returns `true` if the recipe can evolve a weapon of `weapons`.
+    /// `passive_max_level` is the highest level of the recipe's passive.
+    pub fn is_ready(
+        &self,
+        weapons: &WeaponInventoryComponent,
+        passives: &PassiveInventoryComponent,
+        passive_max_level: u32,
+    ) -> bool {
+        weapons
+            .weapons
+            .iter()
+            .any(|weapon| weapon.id == self.weapon && weapon.is_max_level())
+            && passives.level(&self.passive) >= passive_max_level
+    }
+}
+
+/// Every weapon evolution, loaded from `evolutions.ron`.
+#[derive(Resource, Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct EvolutionsResource {
+    pub evolutions: Vec<EvolutionRecipe>,
+}
+
+/// Weapon held by a player, drawn as a child of it and fired with the fire
+/// input.
+#[derive(Component, Clone, Debug)]
+pub struct WeaponComponent {
+    /// Player holding the weapon.
+    pub owner: Entity,
+    pub id: WeaponId,
+    pub data: WeaponData,
+    /// Time left before the weapon can fire again.
+    pub cooldown: Timer,
+    /// Direction the weapon points to in world space. It turns towards the
+    /// aimed direction at `data.turn_rate`.
+    pub aim: Vec2,
+}
+
+impl WeaponComponent {
+    pub fn new(owner: Entity, id: WeaponId, data: WeaponData) -> Self {
+        let mut cooldown =
+            Timer::from_seconds(data.cooldown(), TimerMode::Once);
+        cooldown.tick(cooldown.duration());
+        Self {
+            owner,
+            id,
+            data,
+            cooldown,
+            aim: Vec2::X,
+        }
+    }
+
+    /// Replaces the held weapon. The new weapon is ready to fire and keeps
+    /// pointing in the same direction.
+    pub fn swap(&mut self, id: WeaponId, data: WeaponData) {
+        *self = Self {
+            aim: self.aim,
+            ..Self::new(self.owner, id, data)
+        };
+    }
+
+    /// Turns the weapon towards `direction` by at most `turn_rate` times
+    /// `delta_secs` radians, along the shortest way.
+    pub fn turn_towards(&mut self, direction: Vec2, delta_secs: f32) {
+        self.aim = steer(self.aim, direction, self.data.turn_rate * delta_secs);
+    }
+}
+
+/// Requests the weapon held by `entity` to be swapped for `weapon`.
+#[derive(Event, Clone, Debug)]
+pub struct SwapWeaponEvent {
+    pub entity: Entity,
+    pub weapon: WeaponId,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn data() -> WeaponData {
+        WeaponData {
+            name: "Test".to_string(),
+            sprite: WeaponSprite::IceStaff,
+            scale: 1.0,
+            pivot: Vec2::ZERO,
+            hold_radius: 10.0,
+            turn_rate: PI,
+            damage: 5,
+            damage_multiplier: 1.0,
+            damage_type: DamageType::Physical,
+            fire_rate: 1.0,
+            projectile: ProjectileId::new("Bullet"),
+            targeting: WeaponTargeting::Facing,
+            speed_multiplier: 1.0,
+            projectile_count: 1,
+            range: 100.0,
+            max_level: 2,
+        }
+    }
+
+    #[test]
+    fn equip_respects_cap_and_duplicates() {
+        let mut inventory = WeaponInventoryComponent::default();
+
+        assert!(inventory.equip(WeaponId::new("A"), data(), 2));
+        assert!(!inventory.equip(WeaponId::new("A"), data(), 2));
+        assert!(inventory.equip(WeaponId::new("B"), data(), 2));
+        assert!(!inventory.equip(WeaponId::new("C"), data(), 2));
+        assert_eq!(inventory.weapons.len(), 2);
+    }
+
+    #[test]
+    fn swapped_weapon_is_ready_to_fire() {
+        let mut weapon = WeaponComponent::new(
+            Entity::PLACEHOLDER,
+            WeaponId::new("A"),
+            data(),
+        );
+        weapon.cooldown.reset();
+
+        weapon.swap(
+            WeaponId::new("B"),
+            WeaponData {
+                fire_rate: 4.0,
+                ..data()
+            },
+        );
+
+        assert_eq!(weapon.id, WeaponId::new("B"));
+        assert!(weapon.cooldown.finished());
+        assert_eq!(
+            weapon.cooldown.duration().as_secs_f32(),
+            0.25
+        );
+    }
+
+    #[test]
+    fn evolution_needs_max_weapon_and_passive() {
+        let recipe = EvolutionRecipe {
+            weapon: WeaponId::new("A"),
+            passive: PassiveId::new("P"),
+            evolved: WeaponId::new("B"),
+        };
+        let mut weapons = WeaponInventoryComponent::default();
+        let mut passives = PassiveInventoryComponent::default();
+        weapons.equip(WeaponId::new("A"), data(), 2);
+        passives.add(PassiveId::new("P"), 1);
+
+        assert!(!recipe.is_ready(&weapons, &passives, 1));
+
+        assert!(weapons.level_up(&WeaponId::new("A")));
+        assert!(!weapons.level_up(&WeaponId::new("A")));
+        assert!(recipe.is_ready(&weapons, &passives, 1));
+
+        assert!(weapons.evolve(
+            &recipe.weapon,
+            recipe.evolved.clone(),
+            data()
+        ));
+        assert_eq!(
+            weapons.weapons[0].id,
+            WeaponId::new("B")
+        );
+        assert_eq!(weapons.weapons[0].level, 1);
+    }
+
+    #[test]
+    fn held_weapon_turns_at_turn_rate() {
+        let mut weapon = WeaponComponent::new(
+            Entity::PLACEHOLDER,
+            WeaponId::new("A"),
+            data(),
+        );
+
+        weapon.turn_towards(Vec2::NEG_X, 0.25);
+        assert!(weapon.aim.abs_diff_eq(Vec2::from_angle(PI / 4.0), 1e-5));
+
+        weapon.turn_towards(Vec2::Y, 1.0);
+        assert!(weapon.aim.abs_diff_eq(Vec2::Y, 1e-5));
+    }
+}
@@ -1,14 +1,141 @@
-pub fn add(left: u64, right: u64) -> u64 {
-    left + right
+//! Persistent key/value storage for data that outlives a single run
+//! (e.g. the shadow army roster). Every key is stored as a `.ron` file in the
+//! storage root directory.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use ron::error::SpannedError;
+use ron::ser::PrettyConfig;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use thiserror::Error;
+
+/// Name of the directory created inside the platform's data directory.
+const APP_DIRECTORY: &str = "arise";
+
+#[derive(Error, Debug)]
+pub enum StorageError {
+    #[error("storage io error: {0}")]
+    Io(#[from] io::Error),
+    #[error("failed to serialize `{key}`: {source}")]
+    Serialize { key: String, source: ron::Error },
+    #[error("failed to deserialize `{key}`: {source}")]
+    Deserialize { key: String, source: SpannedError },
+}
+
+/// Handle to a directory where values are saved.
+#[derive(Debug, Clone)]
+pub struct Storage {
+    root: PathBuf,
+}
+
+impl Storage {
+    /// Creates a storage rooted at the given directory.
+    /// The directory is created on the first save.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Storage in the platform's user data directory
+    /// (`$XDG_DATA_HOME/arise`, `~/.local/share/arise` or `%APPDATA%\arise`).
+    /// Returns `None` if no such directory can be determined.
+    pub fn default_location() -> Option<Self> {
+        let data_dir = std::env::var_os("XDG_DATA_HOME")
+            .map(PathBuf::from)
+            .or_else(|| {
+                std::env::var_os("HOME")
+                    .map(|home| Path::new(&home).join(".local").join("share"))
+            })
+            .or_else(|| std::env::var_os("APPDATA").map(PathBuf::from))?;
+
+        Some(Self::new(data_dir.join(APP_DIRECTORY)))
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.root.join(format!("{key}.ron"))
+    }
+
+    /// Loads the value saved under `key`.
+    /// Returns `Ok(None)` if nothing was saved yet.
+    pub fn load<T: DeserializeOwned>(
+        &self,
+        key: &str,
+    ) -> Result<Option<T>, StorageError> {
+        let contents = match fs::read_to_string(self.path(key)) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+
+        ron::from_str(&contents).map(Some).map_err(|source| {
+            StorageError::Deserialize {
+                key: key.to_string(),
+                source,
+            }
+        })
+    }
+
+    /// Saves `value` under `key`, replacing any previous value.
+    pub fn save<T: Serialize>(
+        &self,
+        key: &str,
+        value: &T,
+    ) -> Result<(), StorageError> {
+        let contents =
+            ron::ser::to_string_pretty(value, PrettyConfig::default())
+                .map_err(|source| StorageError::Serialize {
+                    key: key.to_string(),
+                    source,
+                })?;
+
+        fs::create_dir_all(&self.root)?;
+        // Write to a temporary file first so a crash never leaves a truncated save.
+        let temp_path = self.path(&format!("{key}.tmp"));
+        fs::write(&temp_path, contents)?;
+        fs::rename(temp_path, self.path(key))?;
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
+
     use super::*;
 
+    fn temp_storage(name: &str) -> Storage {
+        let root = std::env::temp_dir().join(format!(
+            "arise-storage-{}-{name}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&root);
+        Storage::new(root)
+    }
+
     #[test]
-    fn it_works() {
-        let result = add(2, 2);
-        assert_eq!(result, 4);
+    fn missing_key_loads_none() {
+        let storage = temp_storage("missing");
+        let loaded: Option<u32> = storage.load("nothing").unwrap();
+        assert_eq!(loaded, None);
+    }
+
+    #[test]
+    fn save_then_load_round_trips() {
+        let storage = temp_storage("round_trip");
+        let value = HashMap::from([("Igris".to_string(), 1u32)]);
+
+        storage.save("roster", &value).unwrap();
+        let loaded: Option<HashMap<String, u32>> =
+            storage.load("roster").unwrap();
+
+        assert_eq!(loaded, Some(value));
+        fs::remove_dir_all(storage.root()).unwrap();
     }
 }
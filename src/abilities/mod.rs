@@ -0,0 +1,430 @@
+use bevy::prelude::*;
+use engine::abilities::shadow_monarch::{
+    MonarchFormComponent, ShadowDashComponent, ShadowSummonComponent,
+};
+use engine::abilities::{
+    AbilitiesResource, Ability, AbilityCooldownComponent, AbilityIdComponent,
+    AbilityManaCostComponent,
+    AbilityRankComponent, AbilityRegistry, AbilitySlotIDComponent,
+};
+use engine::events::ability::{AbilityActivatedEvent, AbilityRankUpEvent};
+use engine::events::action::UseSkillEvent;
+use engine::mana::{ManaComponent, ManaRegainComponent};
+use engine::spawnable::mob::SpawnMobEvent;
+use engine::states::app::AppStates;
+
+use crate::abilities::shadow_monarch::{
+    monarch_form_activation_system, monarch_form_expiry_system,
+    shadow_dash_activation_system, shadow_dash_movement_system,
+    shadow_summon_activation_system,
+};
+
+pub mod shadow_monarch;
+
+/// Loads ability data, registers every ability of the game and runs the
+/// shared cooldown/activation logic.
+pub struct AbilitiesPlugin;
+
+impl Plugin for AbilitiesPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<AbilityActivatedEvent>();
+        app.add_event::<AbilityRankUpEvent>();
+        app.add_event::<SpawnMobEvent>();
+
+        app.add_systems(
+            Update,
+            (
+                tick_ability_cooldowns_system,
+                ability_activation_system,
+                ability_rank_up_system,
+            )
+                .chain()
+                .run_if(in_state(AppStates::InGame)),
+        );
+
+        app.register_ability::<ShadowSummonComponent, _>(
+            shadow_summon_activation_system,
+        );
+        app.register_ability::<ShadowDashComponent, _>(
+            shadow_dash_activation_system,
+        );
+        app.register_ability::<MonarchFormComponent, _>(
+            monarch_form_activation_system,
+        );
+
+        app.add_systems(
+            Update,
+            (shadow_dash_movement_system, monarch_form_expiry_system)
+                .run_if(in_state(AppStates::InGame)),
+        );
+    }
+}
+
+/// Extension for registering an ability with the app in one place.
+pub trait AbilityAppExt {
+    /// Adds the ability `A` to the [`AbilityRegistry`] and schedules its
+    /// activation system after the shared [`ability_activation_system`].
+    fn register_ability<A: Ability, M>(
+        &mut self,
+        activation_system: impl IntoSystemConfigs<M>,
+    ) -> &mut Self;
+}
+
+impl AbilityAppExt for App {
+    fn register_ability<A: Ability, M>(
+        &mut self,
+        activation_system: impl IntoSystemConfigs<M>,
+    ) -> &mut Self {
+        self.init_resource::<AbilityRegistry>();
+        self.world_mut()
+            .resource_mut::<AbilityRegistry>()
+            .register::<A>();
+
+        self.add_systems(
+            Update,
+            activation_system
+                .after(ability_activation_system)
+                .run_if(in_state(AppStates::InGame)),
+        )
+    }
+}
+
+/// Advances the cooldown timers of all abilities.
+fn tick_ability_cooldowns_system(
+    time: Res<Time>,
+    mut query: Query<&mut AbilityCooldownComponent>,
+) {
+    for mut cooldown in query.iter_mut() {
+        cooldown.cooldown_timer.tick(time.delta());
+    }
+}
+
+/// Resolves [`UseSkillEvent`]s into the ability entity occupying the requested slot.
+///
+/// If the ability is off cooldown and the caster can pay its mana cost, the
+/// mana is spent, its cooldown is restarted and an [`AbilityActivatedEvent`]
+/// is sent, which the ability's own activation system picks up. Abilities
+/// still on cooldown or lacking mana are ignored. Casters without a
+/// [`ManaComponent`] cast for free.
+pub fn ability_activation_system(
+    mut skill_events: EventReader<UseSkillEvent>,
+    mut activated_events: EventWriter<AbilityActivatedEvent>,
+    mut caster_query: Query<(
+        &Children,
+        Option<&mut ManaComponent>,
+        Option<&mut ManaRegainComponent>,
+    )>,
+    mut ability_query: Query<(
+        &AbilitySlotIDComponent,
+        &mut AbilityCooldownComponent,
+        &AbilityManaCostComponent,
+    )>,
+) {
+    for event in skill_events.read() {
+        let Some(slot) = AbilitySlotIDComponent::from_index(event.slot) else {
+            warn!("Invalid ability slot {}", event.slot);
+            continue;
+        };
+        let Ok((children, mut mana, mut mana_regain)) =
+            caster_query.get_mut(event.entity)
+        else {
+            continue;
+        };
+
+        for &child in children.iter() {
+            let Ok((ability_slot, mut cooldown, mana_cost)) =
+                ability_query.get_mut(child)
+            else {
+                continue;
+            };
+            if *ability_slot != slot {
+                continue;
+            }
+
+            if !cooldown.is_ready() {
+                debug!("Ability in slot {:?} is on cooldown", slot);
+                continue;
+            }
+            if let Some(mana) = mana.as_deref_mut() {
+                if !mana.spend(mana_cost.0) {
+                    debug!(
+                        "Not enough mana for ability in slot {:?} ({}/{})",
+                        slot, mana.current, mana_cost.0
+                    );
+                    continue;
+                }
+                if mana_cost.0 > 0 {
+                    if let Some(regain) = mana_regain.as_deref_mut() {
+                        regain.reset();
+                    }
+                }
+            }
+
+            cooldown.start();
+            activated_events.send(AbilityActivatedEvent {
+                caster: event.entity,
+                ability: child,
+            });
+        }
+    }
+}
+
+/// Applies [`AbilityRankUpEvent`]s through [`AbilityRegistry::rank_up`].
+///
+/// The ability's component is rebuilt from the next rank's data while an
+/// in-progress cooldown keeps its elapsed time.
+///
+/// Nothing sends [`AbilityRankUpEvent`] yet, so abilities stay at rank 1
+/// until a reward or level-up grants ranks.
+fn ability_rank_up_system(
+    mut commands: Commands,
+    mut rank_up_events: EventReader<AbilityRankUpEvent>,
+    ability_registry: Res<AbilityRegistry>,
+    abilities_res: Res<AbilitiesResource>,
+    mut ability_query: Query<(
+        &AbilityIdComponent,
+        &mut AbilityRankComponent,
+        &mut AbilityCooldownComponent,
+    )>,
+) {
+    for event in rank_up_events.read() {
+        let Ok((ability_id, mut rank, mut cooldown)) =
+            ability_query.get_mut(event.ability)
+        else {
+            continue;
+        };
+
+        match ability_registry.rank_up(
+            &mut commands.entity(event.ability),
+            &abilities_res,
+            &ability_id.0,
+            &mut rank,
+            &mut cooldown,
+        ) {
+            Ok(true) => {
+                info!("Ability {} ranked up to {}", ability_id.0, rank.rank)
+            },
+            Ok(false) => {
+                debug!("Ability {} is already at max rank", ability_id.0)
+            },
+            Err(e) => error!("Failed to rank up ability: {}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::ecs::system::RunSystemOnce;
+    use bevy::state::app::StatesPlugin;
+    use engine::abilities::AbilityId;
+    use serde::Deserialize;
+
+    use super::*;
+
+    /// Ability that only exists in these tests.
+    #[derive(Component)]
+    struct TestBoltComponent {
+        damage: u32,
+    }
+
+    #[derive(Deserialize)]
+    struct TestBoltData {
+        damage: u32,
+    }
+
+    impl From<&TestBoltData> for TestBoltComponent {
+        fn from(data: &TestBoltData) -> Self {
+            Self {
+                damage: data.damage,
+            }
+        }
+    }
+
+    impl Ability for TestBoltComponent {
+        const ID: &'static str = "TestBolt";
+        type Data = TestBoltData;
+    }
+
+    /// Damage of every bolt fired by [`test_bolt_activation_system`].
+    #[derive(Resource, Default)]
+    struct BoltsFiredResource(Vec<u32>);
+
+    fn test_bolt_activation_system(
+        mut activated_events: EventReader<AbilityActivatedEvent>,
+        bolt_query: Query<&TestBoltComponent>,
+        mut bolts_fired: ResMut<BoltsFiredResource>,
+    ) {
+        for event in activated_events.read() {
+            if let Ok(bolt) = bolt_query.get(event.ability) {
+                bolts_fired.0.push(bolt.damage);
+            }
+        }
+    }
+
+    fn app() -> App {
+        let abilities: AbilitiesResource = ron::de::from_str(
+            r#"(abilities: {
+                "TestBolt": (ranks: [
+                    (cooldown_time: 2.0, ability: (damage: 7)),
+                    (cooldown_time: 1.5, mana_cost: 5, ability: (damage: 9)),
+                ]),
+            })"#,
+        )
+        .unwrap();
+
+        let mut app = App::new();
+        app.add_plugins(StatesPlugin)
+            .insert_state(AppStates::InGame)
+            .add_event::<UseSkillEvent>()
+            .add_event::<AbilityActivatedEvent>()
+            .add_event::<AbilityRankUpEvent>()
+            .insert_resource(abilities)
+            .init_resource::<BoltsFiredResource>()
+            .add_systems(
+                Update,
+                (ability_activation_system, ability_rank_up_system),
+            )
+            .register_ability::<TestBoltComponent, _>(
+                test_bolt_activation_system,
+            );
+        app
+    }
+
+    /// Spawns a caster holding `TestBolt` in `slot`.
+    fn spawn_caster(app: &mut App, slot: AbilitySlotIDComponent) -> Entity {
+        app.world_mut()
+            .run_system_once(
+                move |mut commands: Commands,
+                      ability_registry: Res<AbilityRegistry>,
+                      abilities_res: Res<AbilitiesResource>| {
+                    commands
+                        .spawn_empty()
+                        .with_children(|parent| {
+                            ability_registry
+                                .spawn_ability(
+                                    parent,
+                                    &abilities_res,
+                                    &AbilityId::new("TestBolt"),
+                                    slot,
+                                )
+                                .unwrap();
+                        })
+                        .id()
+                },
+            )
+            .unwrap()
+    }
+
+    fn use_skill(app: &mut App, entity: Entity, slot: usize) {
+        app.world_mut().send_event(UseSkillEvent { entity, slot });
+        app.update();
+    }
+
+    #[test]
+    fn registered_ability_is_activated_from_any_slot() {
+        let mut app = app();
+        assert!(app
+            .world()
+            .resource::<AbilityRegistry>()
+            .contains(&AbilityId::new("TestBolt")));
+        let caster = spawn_caster(&mut app, AbilitySlotIDComponent::Three);
+
+        use_skill(&mut app, caster, 1);
+        assert!(app.world().resource::<BoltsFiredResource>().0.is_empty());
+
+        use_skill(&mut app, caster, 3);
+        assert_eq!(
+            app.world().resource::<BoltsFiredResource>().0,
+            [7]
+        );
+
+        // The cooldown started with the cast
+        use_skill(&mut app, caster, 3);
+        assert_eq!(
+            app.world().resource::<BoltsFiredResource>().0,
+            [7]
+        );
+    }
+
+    #[test]
+    fn rank_up_keeps_cooldown_progress() {
+        let mut app = app();
+        let caster = spawn_caster(&mut app, AbilitySlotIDComponent::One);
+        use_skill(&mut app, caster, 1);
+        let ability = app.world().get::<Children>(caster).unwrap()[0];
+        app.world_mut()
+            .get_mut::<AbilityCooldownComponent>(ability)
+            .unwrap()
+            .cooldown_timer
+            .tick(Duration::from_secs_f32(0.5));
+
+        app.world_mut().send_event(AbilityRankUpEvent { ability });
+        app.update();
+
+        let world = app.world();
+        assert_eq!(
+            world.get::<AbilityRankComponent>(ability).unwrap().rank,
+            2
+        );
+        let cooldown = world.get::<AbilityCooldownComponent>(ability).unwrap();
+        assert_eq!(cooldown.cooldown_timer.elapsed_secs(), 0.5);
+        assert_eq!(cooldown.cooldown_timer.duration().as_secs_f32(), 1.5);
+        assert!(!cooldown.is_ready());
+        assert_eq!(world.get::<TestBoltComponent>(ability).unwrap().damage, 9);
+        assert_eq!(
+            world.get::<AbilityManaCostComponent>(ability),
+            Some(&AbilityManaCostComponent(5))
+        );
+
+        // Rank 2 is the last rank of the table
+        app.world_mut().send_event(AbilityRankUpEvent { ability });
+        app.update();
+        assert_eq!(
+            app.world().get::<AbilityRankComponent>(ability).unwrap().rank,
+            2
+        );
+    }
+
+    fn mana(app: &App, caster: Entity) -> u32 {
+        app.world().get::<ManaComponent>(caster).unwrap().current
+    }
+
+    #[test]
+    fn cast_without_enough_mana_is_rejected() {
+        let mut app = app();
+        let caster = spawn_caster(&mut app, AbilitySlotIDComponent::One);
+        let ability = app.world().get::<Children>(caster).unwrap()[0];
+        // Rank 2 costs 5 mana
+        app.world_mut().send_event(AbilityRankUpEvent { ability });
+        app.update();
+        app.world_mut().entity_mut(caster).insert((
+            ManaComponent {
+                current: 3,
+                max: 10,
+            },
+            ManaRegainComponent::default(),
+        ));
+
+        use_skill(&mut app, caster, 1);
+        assert!(app.world().resource::<BoltsFiredResource>().0.is_empty());
+        assert_eq!(mana(&app, caster), 3);
+        assert!(app
+            .world()
+            .get::<AbilityCooldownComponent>(ability)
+            .unwrap()
+            .is_ready());
+
+        app.world_mut()
+            .get_mut::<ManaComponent>(caster)
+            .unwrap()
+            .current = 8;
+        use_skill(&mut app, caster, 1);
+        assert_eq!(
+            app.world().resource::<BoltsFiredResource>().0,
+            [9]
+        );
+        assert_eq!(mana(&app, caster), 3);
+    }
+}
@@ -0,0 +1,194 @@
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+use engine::abilities::shadow_monarch::{
+    MonarchFormActiveComponent, MonarchFormComponent, ShadowDashComponent,
+    ShadowDashingComponent, ShadowSummonComponent,
+};
+use engine::aim::AimComponent;
+use engine::combat::HealAppliedEvent;
+use engine::events::ability::AbilityActivatedEvent;
+use engine::health::HealthComponent;
+use engine::player::{
+    PlayerMobilityComponent, PlayerOutgoingDamageComponent,
+    PlayerVelocityComponent,
+};
+use engine::spawnable::mob::shadow::ShadowArmyResource;
+use engine::spawnable::mob::{MobType, SpawnMobEvent};
+
+/// Summons `max_summons` shadows evenly spread on a circle of `max_radius`
+/// around the caster. The shadow army's preferred shadows are raised first.
+pub fn shadow_summon_activation_system(
+    mut activated_events: EventReader<AbilityActivatedEvent>,
+    mut spawn_mob_events: EventWriter<SpawnMobEvent>,
+    army: Res<ShadowArmyResource>,
+    ability_query: Query<&ShadowSummonComponent>,
+    caster_query: Query<&Transform>,
+) {
+    for event in activated_events.read() {
+        let Ok(summon) = ability_query.get(event.ability) else {
+            continue;
+        };
+        let Ok(caster_transform) = caster_query.get(event.caster) else {
+            continue;
+        };
+
+        let center = caster_transform.translation.truncate();
+        let order = army.summon_order(summon.max_summons);
+        for (i, mob_type) in order.into_iter().enumerate() {
+            let angle = TAU * i as f32 / summon.max_summons as f32;
+            spawn_mob_events.send(SpawnMobEvent {
+                mob_type: MobType::Ally(mob_type),
+                position: center + Vec2::from_angle(angle) * summon.max_radius,
+                rotation: Quat::IDENTITY,
+                boss: false,
+                summoned_by: Some(event.caster),
+            });
+        }
+    }
+}
+
+/// Starts a dash on the caster. A zero `direction` in the ability data dashes
+/// in the direction the caster is currently moving (or aiming, if standing still).
+pub fn shadow_dash_activation_system(
+    mut commands: Commands,
+    mut activated_events: EventReader<AbilityActivatedEvent>,
+    ability_query: Query<&ShadowDashComponent>,
+    caster_query: Query<(
+        &PlayerMobilityComponent,
+        &PlayerVelocityComponent,
+        &AimComponent,
+    )>,
+) {
+    for event in activated_events.read() {
+        let Ok(dash) = ability_query.get(event.ability) else {
+            continue;
+        };
+        let Ok((mobility, velocity, aim)) = caster_query.get(event.caster)
+        else {
+            continue;
+        };
+
+        let direction = if dash.direction != Vec2::ZERO {
+            dash.direction.normalize()
+        } else {
+            let moving = Vec2::new(velocity.0, velocity.1).normalize_or_zero();
+            if moving != Vec2::ZERO {
+                moving
+            } else {
+                aim.direction
+            }
+        };
+
+        commands
+            .entity(event.caster)
+            .insert(ShadowDashingComponent {
+                direction,
+                speed: mobility.speed.max_element() * dash.speed_multiplier,
+                timer: Timer::from_seconds(dash.duration, TimerMode::Once),
+            });
+    }
+}
+
+/// Moves dashing entities and ends the dash once its timer finishes.
+pub fn shadow_dash_movement_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(
+        Entity,
+        &mut Transform,
+        &mut ShadowDashingComponent,
+    )>,
+) {
+    for (entity, mut transform, mut dashing) in query.iter_mut() {
+        let step = dashing.direction * dashing.speed * time.delta_secs();
+        transform.translation += step.extend(0.0);
+
+        if dashing.timer.tick(time.delta()).finished() {
+            commands.entity(entity).remove::<ShadowDashingComponent>();
+        }
+    }
+}
+
+/// Enters Monarch Form, granting bonus max health and weapon damage for the
+/// form's duration. Activating while the form is active only refreshes it.
+pub fn monarch_form_activation_system(
+    mut commands: Commands,
+    mut activated_events: EventReader<AbilityActivatedEvent>,
+    mut heal_events: EventWriter<HealAppliedEvent>,
+    ability_query: Query<&MonarchFormComponent>,
+    mut caster_query: Query<(
+        &mut HealthComponent,
+        &mut PlayerOutgoingDamageComponent,
+        Option<&mut MonarchFormActiveComponent>,
+    )>,
+) {
+    for event in activated_events.read() {
+        let Ok(form) = ability_query.get(event.ability) else {
+            continue;
+        };
+        let Ok((mut health, mut outgoing_damage, active)) =
+            caster_query.get_mut(event.caster)
+        else {
+            continue;
+        };
+
+        if let Some(mut active) = active {
+            active.timer = Timer::from_seconds(form.duration, TimerMode::Once);
+            continue;
+        }
+
+        let bonus_health =
+            (health.max as f32 * form.health_percentage / 100.0).round() as u32;
+        let bonus_damage = (outgoing_damage.weapon_damage as f32
+            * form.damage_percentage
+            / 100.0)
+            .round() as u32;
+
+        health.increase_max(bonus_health);
+        let healed = health.heal(bonus_health);
+        if healed > 0 {
+            heal_events.send(HealAppliedEvent {
+                target: event.caster,
+                amount: healed,
+            });
+        }
+        outgoing_damage.weapon_damage += bonus_damage;
+
+        commands
+            .entity(event.caster)
+            .insert(MonarchFormActiveComponent {
+                bonus_health,
+                bonus_damage,
+                timer: Timer::from_seconds(form.duration, TimerMode::Once),
+            });
+    }
+}
+
+/// Reverts the bonuses of Monarch Form once it runs out.
+pub fn monarch_form_expiry_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(
+        Entity,
+        &mut MonarchFormActiveComponent,
+        &mut HealthComponent,
+        &mut PlayerOutgoingDamageComponent,
+    )>,
+) {
+    for (entity, mut active, mut health, mut outgoing_damage) in
+        query.iter_mut()
+    {
+        if !active.timer.tick(time.delta()).finished() {
+            continue;
+        }
+
+        health.decrease_max(active.bonus_health);
+        outgoing_damage.weapon_damage = outgoing_damage
+            .weapon_damage
+            .saturating_sub(active.bonus_damage);
+        commands
+            .entity(entity)
+            .remove::<MonarchFormActiveComponent>();
+    }
+}
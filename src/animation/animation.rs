@@ -5,6 +5,7 @@ use engine::animation::AnimationData;
 use engine::states::player::PlayerState;
 
 #[derive(Resource, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct AnimationsResource {
     pub animations: HashMap<PlayerState, AnimationData>
 }
\ No newline at end of file
@@ -1,7 +1,6 @@
 use std::time::Duration;
 
 use bevy::app::{App, Plugin, Update};
-use bevy::asset::ron::de::from_bytes;
 use bevy::asset::Assets;
 use bevy::ecs::component::Component;
 use bevy::ecs::entity::Entity;
@@ -18,7 +17,9 @@ use engine::animation::AnimationComponent;
 use engine::states;
 use engine::states::app::AppStates;
 use serde::Deserialize;
-use engine::events::animation::AnimationChangeEvent;
+use engine::events::animation::{AnimationChangeEvent, AnimationFrameEvent};
+use engine::states::animation::AnimationStateMachine;
+use engine::states::player::PlayerState;
 use crate::animation::animation::AnimationsResource;
 use crate::animation::player::controller::player_animation_controller_system;
 use crate::player::character::CharactersResource;
@@ -31,13 +32,7 @@ pub struct SpriteAnimationPlugin;
 impl Plugin for SpriteAnimationPlugin {
     fn build(&self, app: &mut App) {
         app.add_event::<AnimationChangeEvent>();
-
-        app.insert_resource(
-            from_bytes::<AnimationsResource>(include_bytes!(
-                "../../assets/data/animations.ron"
-            ))
-            .unwrap(),
-        );
+        app.add_event::<AnimationFrameEvent>();
 
         app.add_systems(
             Update,
@@ -53,18 +48,22 @@ impl Plugin for SpriteAnimationPlugin {
 pub fn animate_sprite_system(
     time: Res<Time>,
     texture_atlas_layouts: Res<Assets<TextureAtlasLayout>>,
+    animations_resource: Res<AnimationsResource>,
+    mut frame_events: EventWriter<AnimationFrameEvent>,
     mut query: Query<(
         Entity,
         &mut AnimationComponent,
         &mut Sprite,
+        Option<&AnimationStateMachine>,
     )>,
 ) {
-    for (entity, mut anim, mut sprite) in query.iter_mut() {
+    for (entity, mut anim, mut sprite, state_machine) in query.iter_mut() {
         if let Some(atlas) = &mut sprite.texture_atlas {
             let just_finished = anim.timer.tick(time.delta()).just_finished();
             if !just_finished {
                 continue;
             }
+            let previous_idx = atlas.index;
 
             if let Some(layout) = texture_atlas_layouts.get(atlas.layout.id()) {
                 let num_frames = layout.len();
@@ -95,6 +94,18 @@ pub fn animate_sprite_system(
                         }
                     },
                 }
+
+                if let Some(state_machine) = state_machine {
+                    if atlas.index != previous_idx {
+                        send_frame_events(
+                            &mut frame_events,
+                            &animations_resource,
+                            entity,
+                            state_machine.current,
+                            atlas.index,
+                        );
+                    }
+                }
             } else {
                 error!(
                     "Could not get texture atlas layout for id: {}",
@@ -104,3 +115,24 @@ pub fn animate_sprite_system(
         }
     }
 }
+
+/// Sends an [`AnimationFrameEvent`] for every tag of `frame` in `clip`.
+pub fn send_frame_events(
+    frame_events: &mut EventWriter<AnimationFrameEvent>,
+    animations_resource: &AnimationsResource,
+    entity: Entity,
+    clip: PlayerState,
+    frame: usize,
+) {
+    let Some(data) = animations_resource.animations.get(&clip) else {
+        return;
+    };
+    for tag in data.tags_at(frame) {
+        frame_events.send(AnimationFrameEvent {
+            entity,
+            clip,
+            frame,
+            tag: tag.to_string(),
+        });
+    }
+}
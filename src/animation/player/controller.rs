@@ -1,18 +1,22 @@
 use assets::player::shadow::PlayerShadowAssets;
 use bevy::log::{debug, info, warn};
 use bevy::prelude::{
-    Entity, EventReader, Query, Res, Sprite, TextureAtlas, Timer, With,
+    Entity, EventReader, EventWriter, Query, Res, Sprite, TextureAtlas, Timer,
+    With,
 };
 use engine::animation::AnimationComponent;
-use engine::events::animation::AnimationChangeEvent;
+use engine::events::animation::{AnimationChangeEvent, AnimationFrameEvent};
+use engine::melee::LightAttackComponent;
 use engine::player::PlayerComponent;
 use engine::states::animation::AnimationStateMachine;
 use engine::states::player::PlayerState;
 
 use crate::animation::animation::AnimationsResource;
+use crate::animation::send_frame_events;
 
 pub fn player_animation_controller_system(
     mut events: EventReader<AnimationChangeEvent>,
+    mut frame_events: EventWriter<AnimationFrameEvent>,
     animations_resource: Res<AnimationsResource>,
     player_assets: Res<PlayerShadowAssets>,
     mut query: Query<
@@ -20,6 +24,7 @@ pub fn player_animation_controller_system(
             &mut AnimationComponent,
             &mut Sprite,
             &mut AnimationStateMachine,
+            Option<&LightAttackComponent>,
         ),
         With<PlayerComponent>,
     >,
@@ -29,6 +34,7 @@ pub fn player_animation_controller_system(
             mut anim_component,
             mut sprite,
             mut state_machine,
+            light_attack,
         )) = query.get_mut(event.entity) else {
             warn!("Failed to get components for AnimationChangeEvent on entity {:?}", event.entity);
             continue;
@@ -66,9 +72,14 @@ pub fn player_animation_controller_system(
                     )
                 },
                 PlayerState::LightAttack => {
+                    player_assets.light_attack_clip(
+                        light_attack.map_or(0, |light_attack| light_attack.step),
+                    )
+                },
+                PlayerState::Charging | PlayerState::HeavyAttack => {
                     (
-                        player_assets.light_attack_image.clone(),
-                        player_assets.light_attack_layout.clone(),
+                        player_assets.heavy_attack_image.clone(),
+                        player_assets.heavy_attack_layout.clone(),
                     )
                 },
             };
@@ -77,6 +88,15 @@ pub fn player_animation_controller_system(
                 Sprite::from_atlas_image(image, TextureAtlas::from(layout));
             anim_component.timer.reset();
             anim_component.timer.unpause();
+
+            // The clip restarts on its first frame
+            send_frame_events(
+                &mut frame_events,
+                &animations_resource,
+                event.entity,
+                event.state,
+                0,
+            );
         } else {
             warn!(
                 "Animation data not found for state {:?}",
@@ -0,0 +1,355 @@
+use std::time::Duration;
+
+use assets::audio::AudioAssets;
+use bevy::prelude::*;
+use bevy_kira_audio::prelude::*;
+use engine::combat::DamageAppliedEvent;
+use engine::events::action::DashEvent;
+use engine::events::reward::{RewardClaimedEvent, RewardSource};
+use engine::objective::PlayerDiedEvent;
+use engine::settings::SettingsResource;
+use engine::states::app::AppStates;
+
+/// Time over which the music of the previous app state fades out while the
+/// next one fades in.
+const MUSIC_CROSSFADE: Duration = Duration::from_millis(1500);
+
+/// Shortest time between two hit sounds, so that a burst of hits plays a
+/// single one.
+const HIT_SFX_INTERVAL: Duration = Duration::from_millis(60);
+
+/// Channel playing the music of the current app state.
+#[derive(Resource)]
+pub struct MusicChannel;
+
+/// Channel playing the sound effects of gameplay events.
+#[derive(Resource)]
+pub struct SfxChannel;
+
+/// Music played for an app state.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MusicTrack {
+    Battle,
+    GameOver,
+}
+
+impl MusicTrack {
+    /// Track of `state`, if it has music.
+    pub fn of(state: &AppStates) -> Option<Self> {
+        match state {
+            AppStates::LoadingAssets => None,
+            AppStates::GameInit | AppStates::InGame => Some(Self::Battle),
+            AppStates::GameOver => Some(Self::GameOver),
+        }
+    }
+}
+
+/// Track currently playing on the [`MusicChannel`].
+#[derive(Resource, Debug, Default)]
+pub struct MusicResource {
+    pub track: Option<MusicTrack>,
+    pub instance: Option<Handle<AudioInstance>>,
+    /// Instances of previous tracks to fade out. They are faded out as soon
+    /// as the audio backend has started them.
+    pub fading_out: Vec<Handle<AudioInstance>>,
+}
+
+/// State of the sound effects kept between frames.
+#[derive(Resource, Debug, Default)]
+pub struct SfxResource {
+    /// Real time at which the last hit sound played.
+    pub last_hit: Option<Duration>,
+}
+
+pub struct GameAudioPlugin;
+
+impl Plugin for GameAudioPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(AudioPlugin)
+            .add_audio_channel::<MusicChannel>()
+            .add_audio_channel::<SfxChannel>()
+            .init_resource::<MusicResource>()
+            .init_resource::<SfxResource>()
+            .add_systems(
+                Update,
+                (
+                    volume_system.run_if(resource_changed::<SettingsResource>),
+                    music_system,
+                    sfx_system.run_if(resource_exists::<AudioAssets>),
+                ),
+            );
+    }
+}
+
+/// Applies the volumes of the settings to the audio channels.
+fn volume_system(
+    settings: Res<SettingsResource>,
+    music_channel: Res<AudioChannel<MusicChannel>>,
+    sfx_channel: Res<AudioChannel<SfxChannel>>,
+) {
+    music_channel.set_volume(settings.volume.music());
+    sfx_channel.set_volume(settings.volume.sfx());
+}
+
+/// Crossfades to the music of the current app state over
+/// [`MUSIC_CROSSFADE`]. States sharing a track keep it playing.
+///
+/// The track only changes once the music is loaded, so a state entered
+/// before [`AudioAssets`] exists starts its music as soon as they do.
+fn music_system(
+    state: Res<State<AppStates>>,
+    audio_assets: Option<Res<AudioAssets>>,
+    music_channel: Res<AudioChannel<MusicChannel>>,
+    mut audio_instances: ResMut<Assets<AudioInstance>>,
+    mut music: ResMut<MusicResource>,
+) {
+    // Queued instances only exist once the backend has started them, so
+    // keep them until then
+    music.fading_out.retain(
+        |handle| match audio_instances.get_mut(handle) {
+            Some(instance) => {
+                instance.stop(AudioTween::linear(MUSIC_CROSSFADE));
+                false
+            },
+            None => music_channel.state(handle) == PlaybackState::Queued,
+        },
+    );
+
+    let track = MusicTrack::of(state.get());
+    if track == music.track {
+        return;
+    }
+    let source = match (track, audio_assets) {
+        (Some(MusicTrack::Battle), Some(audio_assets)) => {
+            Some(audio_assets.battle_music.clone())
+        },
+        (Some(MusicTrack::GameOver), Some(audio_assets)) => {
+            Some(audio_assets.game_over_music.clone())
+        },
+        (Some(_), None) => return,
+        (None, _) => None,
+    };
+
+    let previous = music.instance.take();
+    music.fading_out.extend(previous);
+    music.instance = source.map(|source| {
+        music_channel
+            .play(source)
+            .looped()
+            .fade_in(AudioTween::linear(MUSIC_CROSSFADE))
+            .handle()
+    });
+    music.track = track;
+}
+
+/// Plays the sound effects of the gameplay events of the frame: hits, dashes,
+/// level-ups and player deaths. Hit sounds are played at most once every
+/// [`HIT_SFX_INTERVAL`].
+///
+/// The level-up sound plays on a [`RewardClaimedEvent`] from a level-up.
+/// Nothing sends one until runs grant experience.
+#[allow(clippy::too_many_arguments)]
+fn sfx_system(
+    time: Res<Time<Real>>,
+    audio_assets: Res<AudioAssets>,
+    sfx_channel: Res<AudioChannel<SfxChannel>>,
+    mut sfx: ResMut<SfxResource>,
+    mut damage_applied_events: EventReader<DamageAppliedEvent>,
+    mut dash_events: EventReader<DashEvent>,
+    mut reward_events: EventReader<RewardClaimedEvent>,
+    mut player_died_events: EventReader<PlayerDiedEvent>,
+) {
+    let hit = damage_applied_events.read().any(|event| event.damage > 0);
+    let now = time.elapsed();
+    if hit
+        && sfx
+            .last_hit
+            .is_none_or(|last| now.saturating_sub(last) >= HIT_SFX_INTERVAL)
+    {
+        sfx_channel.play(audio_assets.hit_sfx.clone());
+        sfx.last_hit = Some(now);
+    }
+
+    if dash_events.read().count() > 0 {
+        sfx_channel.play(audio_assets.dash_sfx.clone());
+    }
+    if reward_events
+        .read()
+        .any(|event| event.source == RewardSource::LevelUp)
+    {
+        sfx_channel.play(audio_assets.level_up_sfx.clone());
+    }
+    if player_died_events.read().count() > 0 {
+        sfx_channel.play(audio_assets.death_sfx.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::state::app::StatesPlugin;
+    use engine::combat::DamageType;
+    use engine::settings::VolumeSettings;
+
+    use super::*;
+
+    fn audio_assets() -> AudioAssets {
+        AudioAssets {
+            battle_music: Handle::default(),
+            game_over_music: Handle::default(),
+            hit_sfx: Handle::default(),
+            dash_sfx: Handle::default(),
+            level_up_sfx: Handle::default(),
+            death_sfx: Handle::default(),
+        }
+    }
+
+    fn music_app() -> App {
+        let mut app = App::new();
+        app.add_plugins(StatesPlugin)
+            .init_state::<AppStates>()
+            .init_resource::<Assets<AudioInstance>>()
+            .init_resource::<AudioChannel<MusicChannel>>()
+            .init_resource::<MusicResource>()
+            .add_systems(Update, music_system);
+        app
+    }
+
+    fn enter(app: &mut App, state: AppStates) {
+        app.world_mut()
+            .resource_mut::<NextState<AppStates>>()
+            .set(state);
+        app.update();
+    }
+
+    #[test]
+    fn every_state_but_loading_has_music() {
+        assert_eq!(
+            MusicTrack::of(&AppStates::LoadingAssets),
+            None
+        );
+        assert_eq!(
+            MusicTrack::of(&AppStates::GameInit),
+            Some(MusicTrack::Battle)
+        );
+        assert_eq!(
+            MusicTrack::of(&AppStates::InGame),
+            Some(MusicTrack::Battle)
+        );
+        assert_eq!(
+            MusicTrack::of(&AppStates::GameOver),
+            Some(MusicTrack::GameOver)
+        );
+    }
+
+    #[test]
+    fn volumes_are_scaled_by_master_and_clamped() {
+        let volume = VolumeSettings {
+            master: 0.5,
+            music: 0.5,
+            sfx: 4.0,
+        };
+        assert_eq!(volume.music(), 0.25);
+        assert_eq!(volume.sfx(), 1.0);
+
+        let volume = VolumeSettings {
+            master: 1.0,
+            music: -1.0,
+            sfx: 0.0,
+        };
+        assert_eq!(volume.music(), 0.0);
+        assert_eq!(volume.sfx(), 0.0);
+    }
+
+    #[test]
+    fn states_sharing_a_track_keep_it_playing() {
+        let mut app = music_app();
+        app.insert_resource(audio_assets());
+
+        enter(&mut app, AppStates::GameInit);
+        let music = app.world().resource::<MusicResource>();
+        assert_eq!(music.track, Some(MusicTrack::Battle));
+        let battle = music.instance.clone().unwrap();
+
+        enter(&mut app, AppStates::InGame);
+        let music = app.world().resource::<MusicResource>();
+        assert_eq!(music.instance, Some(battle));
+        assert!(music.fading_out.is_empty());
+    }
+
+    #[test]
+    fn changing_track_fades_out_the_previous_one() {
+        let mut app = music_app();
+        app.insert_resource(audio_assets());
+
+        enter(&mut app, AppStates::InGame);
+        let battle = app
+            .world()
+            .resource::<MusicResource>()
+            .instance
+            .clone()
+            .unwrap();
+
+        enter(&mut app, AppStates::GameOver);
+        let music = app.world().resource::<MusicResource>();
+        assert_eq!(music.track, Some(MusicTrack::GameOver));
+        assert!(music
+            .instance
+            .as_ref()
+            .is_some_and(|game_over| { *game_over != battle }));
+        assert_eq!(music.fading_out, vec![battle]);
+    }
+
+    #[test]
+    fn music_starts_once_loaded() {
+        let mut app = music_app();
+
+        enter(&mut app, AppStates::InGame);
+        let music = app.world().resource::<MusicResource>();
+        assert_eq!(music.track, None);
+        assert_eq!(music.instance, None);
+
+        app.insert_resource(audio_assets());
+        app.update();
+        let music = app.world().resource::<MusicResource>();
+        assert_eq!(music.track, Some(MusicTrack::Battle));
+        assert!(music.instance.is_some());
+    }
+
+    #[test]
+    fn hit_sounds_are_spaced_by_interval() {
+        let mut app = App::new();
+        app.init_resource::<Time<Real>>()
+            .insert_resource(audio_assets())
+            .init_resource::<AudioChannel<SfxChannel>>()
+            .init_resource::<SfxResource>()
+            .add_event::<DamageAppliedEvent>()
+            .add_event::<DashEvent>()
+            .add_event::<RewardClaimedEvent>()
+            .add_event::<PlayerDiedEvent>()
+            .add_systems(Update, sfx_system);
+        let target = app.world_mut().spawn_empty().id();
+        let hit_after = |app: &mut App, delay: Duration| {
+            app.world_mut()
+                .resource_mut::<Time<Real>>()
+                .update_with_duration(delay);
+            app.world_mut().send_event(DamageAppliedEvent {
+                target,
+                damage: 1,
+                damage_type: DamageType::Physical,
+                is_critical: false,
+                source: None,
+            });
+            app.update();
+            app.world().resource::<SfxResource>().last_hit
+        };
+
+        let first = hit_after(&mut app, Duration::ZERO);
+        assert!(first.is_some());
+        let half = HIT_SFX_INTERVAL / 2;
+        assert_eq!(hit_after(&mut app, half), first);
+        assert_eq!(
+            hit_after(&mut app, half),
+            first.map(|first| first + HIT_SFX_INTERVAL)
+        );
+    }
+}
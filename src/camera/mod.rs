@@ -1,15 +1,24 @@
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 use bevy::window::PrimaryWindow;
 use engine::player::PlayerComponent;
 
+use crate::camera::shake::{camera_shake_system, CameraShakeComponent};
 use crate::consts::{CAMERA_HEIGHT, SMOOTHING};
 
+pub mod shake;
+
 pub struct CameraPlugin;
 
 impl Plugin for CameraPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(Startup, setup_camera);
-        app.add_systems(PostUpdate, camera_follow_player);
+        app.add_systems(
+            PostUpdate,
+            (camera_follow_player, camera_shake_system)
+                .chain()
+                .before(TransformSystem::TransformPropagate),
+        );
     }
 }
 
@@ -17,12 +26,17 @@ impl Plugin for CameraPlugin {
 pub struct GameCamera;
 
 fn setup_camera(mut commands: Commands) {
-    commands.spawn((Camera2d, PrimaryWindow, GameCamera));
+    commands.spawn((
+        Camera2d,
+        PrimaryWindow,
+        GameCamera,
+        CameraShakeComponent::default(),
+    ));
 }
 
 fn camera_follow_player(
     mut camera_query: Query<
-        &mut Transform,
+        (&mut Transform, Option<&CameraShakeComponent>),
         (
             With<GameCamera>,
             Without<PlayerComponent>,
@@ -39,13 +53,44 @@ fn camera_follow_player(
     let Ok(player_transform) = player_query.get_single() else {
         return;
     };
-    let Ok(mut camera_transform) = camera_query.get_single_mut() else {
+    let Ok((mut camera_transform, shake)) = camera_query.get_single_mut()
+    else {
         return;
     };
 
+    // Follow from the position the camera would have without its shake
+    let shake_offset = shake.map_or(Vec2::ZERO, |shake| shake.offset);
     let player_position = player_transform.translation.truncate();
-    let camera_position = camera_transform.translation.truncate();
+    let camera_position =
+        camera_transform.translation.truncate() - shake_offset;
     let new_camera_position = camera_position.lerp(player_position, SMOOTHING);
 
-    camera_transform.translation = new_camera_position.extend(CAMERA_HEIGHT);
+    camera_transform.translation =
+        (new_camera_position + shake_offset).extend(CAMERA_HEIGHT);
+}
+
+/// Resolves the cursor's position in world space through the [`GameCamera`].
+#[derive(SystemParam)]
+pub struct CursorWorldPosition<'w, 's> {
+    windows_query: Query<'w, 's, &'static Window, With<PrimaryWindow>>,
+    camera_query: Query<
+        'w,
+        's,
+        (&'static Camera, &'static GlobalTransform),
+        With<GameCamera>,
+    >,
+}
+
+impl CursorWorldPosition<'_, '_> {
+    /// Returns the cursor's world position, or `None` if the cursor is
+    /// outside the window.
+    pub fn get(&self) -> Option<Vec2> {
+        let window = self.windows_query.get_single().ok()?;
+        let (camera, camera_transform) = self.camera_query.get_single().ok()?;
+        let cursor_screen_pos = window.cursor_position()?;
+
+        camera
+            .viewport_to_world_2d(camera_transform, cursor_screen_pos)
+            .ok()
+    }
 }
@@ -0,0 +1,62 @@
+use bevy::prelude::*;
+use engine::settings::SettingsResource;
+
+/// Trauma-based shake of the [`GameCamera`](super::GameCamera).
+///
+/// Hits add trauma, which decays over time. The camera is offset and turned
+/// by an amount growing with the square of the trauma, so small hits barely
+/// move it while big ones shake it hard.
+#[derive(Component, Debug, Default)]
+pub struct CameraShakeComponent {
+    /// From 0 (still) to 1 (full shake).
+    pub trauma: f32,
+    /// Offset currently applied on top of the camera's position.
+    pub offset: Vec2,
+}
+
+impl CameraShakeComponent {
+    /// Adds `amount` of trauma, up to 1.
+    pub fn add_trauma(&mut self, amount: f32) {
+        self.trauma = (self.trauma + amount).clamp(0.0, 1.0);
+    }
+}
+
+/// Decays the trauma of the camera and shakes it accordingly.
+///
+/// Real time is used so that the camera keeps shaking during a hit-stop.
+pub fn camera_shake_system(
+    time: Res<Time<Real>>,
+    settings: Res<SettingsResource>,
+    mut camera_query: Query<(
+        &mut Transform,
+        &mut CameraShakeComponent,
+    )>,
+) {
+    let feedback = &settings.hit_feedback;
+    for (mut transform, mut shake) in camera_query.iter_mut() {
+        shake.trauma =
+            (shake.trauma - feedback.trauma_decay * time.delta_secs()).max(0.0);
+        if !feedback.screen_shake {
+            shake.trauma = 0.0;
+        }
+
+        let amount = shake.trauma.powi(2) * feedback.shake_intensity;
+        let t = time.elapsed_secs();
+        let offset = Vec2::new(noise(t, 0.0), noise(t, 10.0))
+            * feedback.max_shake_offset
+            * amount;
+
+        transform.translation += (offset - shake.offset).extend(0.0);
+        transform.rotation = Quat::from_rotation_z(
+            noise(t, 20.0) * feedback.max_shake_angle * amount,
+        );
+        shake.offset = offset;
+    }
+}
+
+/// Smooth pseudo-random value between -1 and 1 at time `t`, different for
+/// every `seed`.
+fn noise(t: f32, seed: f32) -> f32 {
+    let t = t * 30.0 + seed;
+    ((t.sin() + (t * 2.3 + 1.7).sin() * 0.5) / 1.5).clamp(-1.0, 1.0)
+}
@@ -0,0 +1,674 @@
+use std::collections::HashSet;
+use std::fmt;
+
+use assets::player::shadow::PlayerShadowAssets;
+use bevy::prelude::*;
+use engine::abilities::{
+    AbilitiesResource, AbilityDescriptionsResource, AbilityError, AbilityId,
+    AbilityRegistry,
+};
+use engine::character::CharacterType;
+use engine::input::InputsResource;
+use engine::melee::{HITBOX_FRAME_TAG, SWING_FRAME_TAG};
+use engine::passive::{PassiveId, PassivesResource};
+use engine::spawnable::mob::shadow::ShadowExtractionsResource;
+use engine::spawnable::mob::{
+    EnemyMobType, MobSpawnsResource, MobStatsResource,
+};
+use engine::spawnable::projectile::behavior::{
+    ProjectileId, ProjectilesResource,
+};
+use engine::states::player::PlayerState;
+use engine::status_effect::StatusEffectsResource;
+use engine::weapon::{EvolutionsResource, WeaponId, WeaponsResource};
+use ron::error::SpannedError;
+use serde::de::DeserializeOwned;
+use strum::IntoEnumIterator;
+use thiserror::Error;
+
+use crate::animation::animation::AnimationsResource;
+use crate::game::resources::GameResource;
+use crate::options::input::InputBindings;
+use crate::player::character::CharactersResource;
+use crate::ui::damage_number::DamageNumbersResource;
+
+/// Parses a file of `assets/data`, recording a parse error in the report.
+/// `$overrides` may replace the contents of the compiled file.
+macro_rules! parse_data_file {
+    ($report:ident, $overrides:expr, $file:literal) => {
+        parse::<_>(
+            $file,
+            $overrides($file).unwrap_or(include_bytes!(concat!(
+                "../../assets/data/",
+                $file
+            ))),
+            &mut $report,
+        )
+    };
+}
+
+/// A problem found in the data files.
+#[derive(Error, Debug)]
+pub enum DataError {
+    #[error("{file}: {source}")]
+    Parse {
+        file: &'static str,
+        source: SpannedError,
+    },
+    #[error("characters.ron: no entry for character {0:?}")]
+    MissingCharacter(CharacterType),
+    #[error(
+        "characters.ron: entry {key:?} has character_type {character_type:?}"
+    )]
+    CharacterTypeMismatch {
+        key: CharacterType,
+        character_type: CharacterType,
+    },
+    #[error("characters.ron: character {0:?} has no sprite assets")]
+    MissingCharacterAssets(CharacterType),
+    #[error(
+        "characters.ron: character {character:?} uses ability `{ability}` in \
+         slot {slot}, but abilities.ron has no entry for it"
+    )]
+    MissingSlotAbility {
+        character: CharacterType,
+        slot: usize,
+        ability: AbilityId,
+    },
+    #[error(
+        "characters.ron: character {character:?} fires projectile \
+         `{projectile}`, but projectiles.ron has no entry for it"
+    )]
+    MissingProjectile {
+        character: CharacterType,
+        projectile: ProjectileId,
+    },
+    #[error(
+        "mob_stats.ron: enemy {mob_type:?} fires projectile `{projectile}`, \
+         but projectiles.ron has no entry for it"
+    )]
+    MissingMobProjectile {
+        mob_type: EnemyMobType,
+        projectile: ProjectileId,
+    },
+    #[error("mobs.ron: spawn_interval must be greater than zero")]
+    InvalidSpawnInterval,
+    #[error("mobs.ron: the enemy spawn table has no entry with a weight")]
+    EmptyEnemySpawns,
+    #[error(
+        "mobs.ron: enemy {0:?} is spawned, but mob_stats.ron has no entry \
+         for it"
+    )]
+    MissingSpawnedMobStats(EnemyMobType),
+    #[error(
+        "characters.ron: character {character:?} holds weapon `{weapon}`, \
+         but weapons.ron has no entry for it"
+    )]
+    MissingWeapon {
+        character: CharacterType,
+        weapon: WeaponId,
+    },
+    #[error(
+        "characters.ron: character {character:?} starts with weapon \
+         `{weapon}`, but weapons.ron has no entry for it"
+    )]
+    MissingStartingWeapon {
+        character: CharacterType,
+        weapon: WeaponId,
+    },
+    #[error(
+        "characters.ron: character {character:?} starts with {count} \
+         weapons, more than max_weapons ({max})"
+    )]
+    TooManyStartingWeapons {
+        character: CharacterType,
+        count: usize,
+        max: usize,
+    },
+    #[error("weapons.ron: weapon `{0}` has a fire rate of zero or less")]
+    InvalidFireRate(WeaponId),
+    #[error(
+        "weapons.ron: weapon `{weapon}` fires projectile `{projectile}`, but \
+         projectiles.ron has no entry for it"
+    )]
+    MissingWeaponProjectile {
+        weapon: WeaponId,
+        projectile: ProjectileId,
+    },
+    #[error(
+        "evolutions.ron: a recipe uses weapon `{0}`, which has no entry in \
+         weapons.ron"
+    )]
+    MissingEvolutionWeapon(WeaponId),
+    #[error(
+        "evolutions.ron: a recipe uses passive `{0}`, which has no entry in \
+         passives.ron"
+    )]
+    MissingEvolutionPassive(PassiveId),
+    #[error("evolutions.ron: weapon `{0}` has more than one evolution")]
+    DuplicateEvolution(WeaponId),
+    #[error("evolutions.ron: weapon `{0}` evolves into itself")]
+    SelfEvolution(WeaponId),
+    #[error("damage_numbers.ron: pool_size must be at least 1")]
+    EmptyDamageNumberPool,
+    #[error("characters.ron: character {0:?} has an empty light attack combo")]
+    EmptyLightAttackCombo(CharacterType),
+    #[error("abilities.ron: {0}")]
+    Ability(AbilityError),
+    #[error(
+        "ability_descriptions.ron: description of `{0}`, which has no entry \
+         in abilities.ron"
+    )]
+    UnknownAbilityDescription(AbilityId),
+    #[error("animations.ron: no animation for player state {0:?}")]
+    MissingAnimation(PlayerState),
+    #[error(
+        "animations.ron: character {character:?} spawns a hitbox on frame \
+         {frame} of {clip:?}, but the frame has no `hitbox` tag"
+    )]
+    UntaggedHitboxFrame {
+        character: CharacterType,
+        clip: PlayerState,
+        frame: usize,
+    },
+    #[error("animations.ron: frame 0 of LightAttack has no `swing` tag")]
+    UntaggedSwingFrame,
+}
+
+/// Every problem found while loading the data files, reported at once.
+#[derive(Debug, Default)]
+pub struct DataReport(Vec<DataError>);
+
+impl DataReport {
+    pub fn push(&mut self, error: DataError) {
+        self.0.push(error);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl fmt::Display for DataReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "found {} problem(s) in assets/data:",
+            self.0.len()
+        )?;
+        for error in &self.0 {
+            writeln!(f, "  - {error}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for DataReport {}
+
+/// Every data file of `assets/data` that is compiled into the game.
+pub struct GameData {
+    pub game_parameters: GameResource,
+    pub inputs: InputsResource,
+    pub characters: CharactersResource,
+    pub animations: AnimationsResource,
+    pub abilities: AbilitiesResource,
+    pub ability_descriptions: AbilityDescriptionsResource,
+    pub status_effects: StatusEffectsResource,
+    pub mob_stats: MobStatsResource,
+    pub mob_spawns: MobSpawnsResource,
+    pub shadow_extractions: ShadowExtractionsResource,
+    pub projectiles: ProjectilesResource,
+    pub weapons: WeaponsResource,
+    pub passives: PassivesResource,
+    pub evolutions: EvolutionsResource,
+    pub damage_numbers: DamageNumbersResource,
+}
+
+impl GameData {
+    /// Parses and cross-checks every data file.
+    ///
+    /// Abilities are checked against `registry`, if given. All problems are
+    /// collected and returned together instead of stopping at the first one.
+    pub fn load(
+        registry: Option<&AbilityRegistry>,
+    ) -> Result<Self, DataReport> {
+        Self::load_with(registry, |_| None)
+    }
+
+    /// Same as [`GameData::load`], but the files for which `overrides`
+    /// returns contents are parsed from those instead.
+    fn load_with(
+        registry: Option<&AbilityRegistry>,
+        overrides: impl Fn(&str) -> Option<&'static [u8]>,
+    ) -> Result<Self, DataReport> {
+        let mut report = DataReport::default();
+
+        let (
+            Some(game_parameters),
+            Some(inputs),
+            Some(characters),
+            Some(animations),
+            Some(abilities),
+            Some(ability_descriptions),
+            Some(status_effects),
+            Some(mob_stats),
+            Some(mob_spawns),
+            Some(shadow_extractions),
+            Some(projectiles),
+            Some(weapons),
+            Some(passives),
+            Some(evolutions),
+            Some(damage_numbers),
+        ) = (
+            parse_data_file!(report, overrides, "game_parameters.ron"),
+            parse_data_file!(report, overrides, "input.ron")
+                .map(|bindings: InputBindings| InputsResource::from(bindings)),
+            parse_data_file!(report, overrides, "characters.ron"),
+            parse_data_file!(report, overrides, "animations.ron"),
+            parse_data_file!(report, overrides, "abilities.ron"),
+            parse_data_file!(
+                report,
+                overrides,
+                "ability_descriptions.ron"
+            ),
+            parse_data_file!(report, overrides, "status_effects.ron"),
+            parse_data_file!(report, overrides, "mob_stats.ron"),
+            parse_data_file!(report, overrides, "mobs.ron"),
+            parse_data_file!(
+                report,
+                overrides,
+                "shadow_extractions.ron"
+            ),
+            parse_data_file!(report, overrides, "projectiles.ron"),
+            parse_data_file!(report, overrides, "weapons.ron"),
+            parse_data_file!(report, overrides, "passives.ron"),
+            parse_data_file!(report, overrides, "evolutions.ron"),
+            parse_data_file!(report, overrides, "damage_numbers.ron"),
+        )
+        else {
+            return Err(report);
+        };
+
+        let data = Self {
+            game_parameters,
+            inputs,
+            characters,
+            animations,
+            abilities,
+            ability_descriptions,
+            status_effects,
+            mob_stats,
+            mob_spawns,
+            shadow_extractions,
+            projectiles,
+            weapons,
+            passives,
+            evolutions,
+            damage_numbers,
+        };
+        data.validate(registry, &mut report);
+
+        if report.is_empty() {
+            Ok(data)
+        } else {
+            Err(report)
+        }
+    }
+
+    /// Cross-checks references between the data files.
+    pub fn validate(
+        &self,
+        registry: Option<&AbilityRegistry>,
+        report: &mut DataReport,
+    ) {
+        for character_type in CharacterType::iter() {
+            if !self.characters.characters.contains_key(&character_type) {
+                report.push(DataError::MissingCharacter(
+                    character_type,
+                ));
+            }
+        }
+
+        for (key, character) in &self.characters.characters {
+            if character.character_type != *key {
+                report.push(DataError::CharacterTypeMismatch {
+                    key: *key,
+                    character_type: character.character_type,
+                });
+            }
+            if !PlayerShadowAssets::CHARACTERS.contains(key) {
+                report.push(DataError::MissingCharacterAssets(*key));
+            }
+            if self.projectiles.get(&character.projectile).is_none() {
+                report.push(DataError::MissingProjectile {
+                    character: *key,
+                    projectile: character.projectile.clone(),
+                });
+            }
+            if self.weapons.get(&character.weapon).is_none() {
+                report.push(DataError::MissingWeapon {
+                    character: *key,
+                    weapon: character.weapon.clone(),
+                });
+            }
+            for weapon in &character.starting_weapons {
+                if self.weapons.get(weapon).is_none() {
+                    report.push(DataError::MissingStartingWeapon {
+                        character: *key,
+                        weapon: weapon.clone(),
+                    });
+                }
+            }
+            let max_weapons = self.game_parameters.max_weapons;
+            if character.starting_weapons.len() > max_weapons {
+                report.push(DataError::TooManyStartingWeapons {
+                    character: *key,
+                    count: character.starting_weapons.len(),
+                    max: max_weapons,
+                });
+            }
+            if character.light_attack.combo.is_empty() {
+                report.push(DataError::EmptyLightAttackCombo(*key));
+            }
+
+            let attacks = character
+                .light_attack
+                .combo
+                .iter()
+                .map(|attack| (PlayerState::LightAttack, attack))
+                .chain([(
+                    PlayerState::HeavyAttack,
+                    &character.heavy_attack.attack,
+                )]);
+            for (clip, attack) in attacks {
+                let frame = attack.active_frames.0;
+                if !self.has_frame_tag(clip, frame, HITBOX_FRAME_TAG) {
+                    report.push(DataError::UntaggedHitboxFrame {
+                        character: *key,
+                        clip,
+                        frame,
+                    });
+                }
+            }
+
+            let slots = [
+                &character.slot_1_ability,
+                &character.slot_2_ability,
+                &character.slot_3_ability,
+            ];
+            for (index, ability) in slots.into_iter().enumerate() {
+                let Some(ability) = ability else {
+                    continue;
+                };
+                if !self.abilities.abilities.contains_key(ability) {
+                    report.push(DataError::MissingSlotAbility {
+                        character: *key,
+                        slot: index + 1,
+                        ability: ability.clone(),
+                    });
+                }
+            }
+        }
+
+        if self.mob_spawns.spawn_interval <= 0.0 {
+            report.push(DataError::InvalidSpawnInterval);
+        }
+        if self
+            .mob_spawns
+            .enemies
+            .iter()
+            .all(|spawn| spawn.weight == 0)
+        {
+            report.push(DataError::EmptyEnemySpawns);
+        }
+        let spawned = self
+            .mob_spawns
+            .enemies
+            .iter()
+            .map(|spawn| spawn.mob_type)
+            .chain(self.mob_spawns.bosses.iter().map(|boss| boss.mob_type))
+            .collect::<HashSet<_>>();
+        for mob_type in spawned {
+            if self.mob_stats.enemy(mob_type).is_none() {
+                report.push(DataError::MissingSpawnedMobStats(
+                    mob_type,
+                ));
+            }
+        }
+
+        for (id, weapon) in &self.weapons.weapons {
+            if weapon.fire_rate <= 0.0 {
+                report.push(DataError::InvalidFireRate(id.clone()));
+            }
+            if self.projectiles.get(&weapon.projectile).is_none() {
+                report.push(DataError::MissingWeaponProjectile {
+                    weapon: id.clone(),
+                    projectile: weapon.projectile.clone(),
+                });
+            }
+        }
+
+        let mut evolving = HashSet::new();
+        for recipe in &self.evolutions.evolutions {
+            for weapon in [&recipe.weapon, &recipe.evolved] {
+                if self.weapons.get(weapon).is_none() {
+                    report.push(DataError::MissingEvolutionWeapon(
+                        weapon.clone(),
+                    ));
+                }
+            }
+            if self.passives.get(&recipe.passive).is_none() {
+                report.push(DataError::MissingEvolutionPassive(
+                    recipe.passive.clone(),
+                ));
+            }
+            if recipe.weapon == recipe.evolved {
+                report.push(DataError::SelfEvolution(recipe.weapon.clone()));
+            }
+            if !evolving.insert(&recipe.weapon) {
+                report.push(DataError::DuplicateEvolution(
+                    recipe.weapon.clone(),
+                ));
+            }
+        }
+
+        if self.damage_numbers.pool_size == 0 {
+            report.push(DataError::EmptyDamageNumberPool);
+        }
+
+        for (mob_type, stats) in &self.mob_stats.enemies {
+            let Some(ranged_attack) = &stats.ranged_attack else {
+                continue;
+            };
+            if self.projectiles.get(&ranged_attack.projectile).is_none() {
+                report.push(DataError::MissingMobProjectile {
+                    mob_type: *mob_type,
+                    projectile: ranged_attack.projectile.clone(),
+                });
+            }
+        }
+
+        if let Some(registry) = registry {
+            for (id, ability) in &self.abilities.abilities {
+                let Some(registered) = registry.get(id) else {
+                    report.push(DataError::Ability(
+                        AbilityError::Unregistered(id.clone()),
+                    ));
+                    continue;
+                };
+                for rank in &ability.ranks {
+                    if let Err(error) = (registered.load)(rank) {
+                        report.push(DataError::Ability(error));
+                    }
+                }
+            }
+        }
+
+        for id in self.ability_descriptions.descriptions.keys() {
+            if !self.abilities.abilities.contains_key(id) {
+                report.push(DataError::UnknownAbilityDescription(
+                    id.clone(),
+                ));
+            }
+        }
+
+        for state in PlayerState::iter() {
+            if !self.animations.animations.contains_key(&state) {
+                report.push(DataError::MissingAnimation(state));
+            }
+        }
+        if !self.has_frame_tag(
+            PlayerState::LightAttack,
+            0,
+            SWING_FRAME_TAG,
+        ) {
+            report.push(DataError::UntaggedSwingFrame);
+        }
+    }
+
+    /// Returns `true` if `frame` of `clip` is tagged with `tag`.
+    fn has_frame_tag(
+        &self,
+        clip: PlayerState,
+        frame: usize,
+        tag: &str,
+    ) -> bool {
+        self.animations
+            .animations
+            .get(&clip)
+            .is_some_and(|data| data.tags_at(frame).any(|t| t == tag))
+    }
+
+    /// Inserts every data file as a resource.
+    pub fn insert_into(self, app: &mut App) {
+        app.insert_resource(self.game_parameters)
+            .insert_resource(self.inputs)
+            .insert_resource(self.characters)
+            .insert_resource(self.animations)
+            .insert_resource(self.abilities)
+            .insert_resource(self.ability_descriptions)
+            .insert_resource(self.status_effects)
+            .insert_resource(self.mob_stats)
+            .insert_resource(self.mob_spawns)
+            .insert_resource(self.shadow_extractions)
+            .insert_resource(self.projectiles)
+            .insert_resource(self.weapons)
+            .insert_resource(self.passives)
+            .insert_resource(self.evolutions)
+            .insert_resource(self.damage_numbers);
+    }
+}
+
+fn parse<T: DeserializeOwned>(
+    file: &'static str,
+    bytes: &[u8],
+    report: &mut DataReport,
+) -> Option<T> {
+    ron::de::from_bytes(bytes)
+        .map_err(|source| report.push(DataError::Parse { file, source }))
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use engine::abilities::shadow_monarch::{
+        MonarchFormComponent, ShadowDashComponent, ShadowSummonComponent,
+    };
+
+    use super::*;
+
+    #[test]
+    fn shipped_data_is_valid() {
+        let mut registry = AbilityRegistry::default();
+        registry.register::<ShadowSummonComponent>();
+        registry.register::<ShadowDashComponent>();
+        registry.register::<MonarchFormComponent>();
+
+        if let Err(report) = GameData::load(Some(&registry)) {
+            panic!("{report}");
+        }
+    }
+
+    #[test]
+    fn unknown_fields_are_rejected() {
+        let mut report = DataReport::default();
+        let parsed = parse::<engine::animation::AnimationData>(
+            "animations.ron",
+            b"(frame_duration: 0.1, mode: Once, direction: Forward, speed: 2.0)",
+            &mut report,
+        );
+
+        assert!(parsed.is_none());
+        assert!(matches!(
+            &report.0[..],
+            [DataError::Parse {
+                file: "animations.ron",
+                ..
+            }]
+        ));
+    }
+
+    #[test]
+    fn broken_files_are_reported_together() {
+        let result = GameData::load_with(None, |file| match file {
+            "input.ron" => Some(&b"(player_keyboard: [(Jump, Space)])"[..]),
+            "mob_stats.ron" => Some(&b"(enemies: {})"[..]),
+            _ => None,
+        });
+        let Err(report) = result else {
+            panic!("broken files were accepted");
+        };
+
+        let files = report
+            .0
+            .iter()
+            .map(|error| match error {
+                DataError::Parse { file, .. } => *file,
+                error => panic!("unexpected error: {error}"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(files, ["input.ron", "mob_stats.ron"]);
+    }
+
+    #[test]
+    fn broken_evolutions_are_reported() {
+        let mut data = GameData::load(None).unwrap();
+        let recipe = data.evolutions.evolutions[0].clone();
+        data.passives.passives.remove(&recipe.passive);
+        data.evolutions.evolutions.push(recipe);
+
+        let mut report = DataReport::default();
+        data.validate(None, &mut report);
+
+        assert!(report.0.iter().any(|error| matches!(
+            error,
+            DataError::MissingEvolutionPassive(_)
+        )));
+        assert!(report
+            .0
+            .iter()
+            .any(|error| matches!(error, DataError::DuplicateEvolution(_))));
+    }
+
+    #[test]
+    fn every_problem_is_reported() {
+        let mut data = GameData::load(None).unwrap();
+        data.animations.animations.remove(&PlayerState::Idle);
+        data.animations.animations.remove(&PlayerState::Running);
+        data.abilities.abilities.clear();
+
+        let mut report = DataReport::default();
+        data.validate(None, &mut report);
+
+        let missing_animations = report
+            .0
+            .iter()
+            .filter(|error| matches!(error, DataError::MissingAnimation(_)))
+            .count();
+        assert_eq!(missing_animations, 2);
+        assert!(report.0.iter().any(|error| matches!(
+            error,
+            DataError::MissingSlotAbility { .. }
+        )));
+    }
+}
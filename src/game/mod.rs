@@ -1,19 +1,27 @@
 use bevy::prelude::*;
-use bevy::scene::ron::de::from_bytes;
-use resources::GameResource;
+use engine::abilities::AbilityRegistry;
+
+use crate::game::data::GameData;
 
 pub mod counters;
+pub mod data;
 pub mod resources;
 
+/// Loads every file of `assets/data` and inserts it as a resource.
+///
+/// The files are loaded in [`Plugin::finish`], once every plugin registered
+/// its abilities, so that the whole data set can be validated. All problems
+/// are reported together in a single panic message.
 pub struct GameResourcePlugin;
 
 impl Plugin for GameResourcePlugin {
-    fn build(&self, app: &mut App) {
-        app.insert_resource(
-            from_bytes::<GameResource>(include_bytes!(
-                "../../assets/data/game_parameters.ron"
-            ))
-            .unwrap(),
-        );
+    fn build(&self, _app: &mut App) {}
+
+    fn finish(&self, app: &mut App) {
+        let registry = app.world().get_resource::<AbilityRegistry>();
+        match GameData::load(registry) {
+            Ok(data) => data.insert_into(app),
+            Err(report) => panic!("{report}"),
+        }
     }
 }
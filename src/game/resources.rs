@@ -2,10 +2,17 @@ use bevy::prelude::*;
 use serde::Deserialize;
 
 #[derive(Resource, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct GameResource {
     /// Maximum possible projectiles for 1 of the player/mobs shots. Mainly kept low for perf and as
     /// a hard cap (along with fire rate) on how much of a "bullet hell" each mob/player creates.
     pub max_player_projectiles: u16,
+    /// Maximum auto-firing weapons a player can hold at once
+    pub max_weapons: usize,
+    /// Widest arc (in radians) the projectiles of one shot may spread across
+    pub max_spread_arc: f32,
+    /// Angle (in radians) shared between the projectiles of one shot
+    pub projectile_gap: f32,
     /// Maximum possible speed of an entity
     pub max_speed: f32,
     /// Distance between the center of the screen and the player spawn point
@@ -16,8 +23,21 @@ pub struct GameResource {
     pub stop_threshold: f32,
     /// Range of mouse scanning
     pub scan_range: f32,
+    /// Distance within which the `AutoNearest` aim mode picks an enemy
+    pub auto_aim_range: f32,
+    /// Tilt of the right stick below which it is considered at rest
+    pub stick_deadzone: f32,
+    /// Size of the arena, centered on the world origin
+    pub arena_size: Vec2,
+    /// Seconds a hit with full knockback stuns its target
+    pub hit_stun_duration: f32,
+    /// How quickly a knocked back mob loses its knockback velocity (per second)
+    pub knockback_friction: f32,
     /// Maximum amount of player inputs to the game
     max_players: u8,
+    /// Seed of the combat RNG. Random on every run if not set.
+    #[serde(default)]
+    pub rng_seed: Option<u64>,
 }
 
 impl GameResource {
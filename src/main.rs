@@ -10,7 +10,9 @@ use player::systems::combat;
 use crate::consts::SIMULATION_SCALE_FACTOR;
 use crate::game::counters;
 
+mod abilities;
 mod animation;
+mod audio;
 mod camera;
 mod consts;
 mod dev;
@@ -87,6 +89,11 @@ impl PluginGroup for ArisePlugins {
             .add(camera::CameraPlugin)
             .add(combat::CombatPlugin)
             .add(player::PlayerPlugin)
+            .add(abilities::AbilitiesPlugin)
+            .add(spawnable::mob::MobPlugin)
+            .add(spawnable::projectile::ProjectilePlugin)
             .add(weapon::WeaponPlugin)
+            .add(ui::UiPlugin)
+            .add(audio::GameAudioPlugin)
     }
 }
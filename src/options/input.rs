@@ -1,14 +1,13 @@
-use std::fs::read_to_string;
-use std::path::PathBuf;
-
 use bevy::prelude::*;
 use engine::input::{InputsResource, PlayerAction};
 use leafwing_input_manager::prelude::{ActionState, InputMap};
 use leafwing_input_manager::InputManagerBundle;
-use ron::from_str;
 use serde::Deserialize;
 
+/// Key and mouse bindings of the player's actions, loaded from `input.ron`
+/// with the other data files.
 #[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct InputBindings {
     pub player_keyboard: Vec<(PlayerAction, KeyCode)>,
     pub player_mouse: Vec<(PlayerAction, MouseButton)>,
@@ -23,14 +22,3 @@ impl From<InputBindings> for InputsResource {
         }
     }
 }
-
-pub fn get_input_bindings() -> InputBindings {
-    let mut config_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-    config_path.push("assets");
-    config_path.push("data");
-
-    from_str::<InputBindings>(
-        &read_to_string(config_path.join("input.ron")).unwrap(),
-    )
-    .unwrap()
-}
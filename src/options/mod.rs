@@ -1,16 +1,31 @@
 use bevy::prelude::*;
-use engine::input::InputsResource;
-use input::get_input_bindings;
+use engine::settings::SettingsResource;
+use settings::{change_settings_system, ChangeSettingsEvent};
 
 pub mod display;
 pub mod input;
+pub mod settings;
 
 pub struct OptionsPlugin;
 
 impl Plugin for OptionsPlugin {
     fn build(&self, app: &mut App) {
-        app.insert_resource(InputsResource::from(
-            get_input_bindings(),
-        ));
+        app.init_resource::<SettingsResource>()
+            .add_event::<ChangeSettingsEvent>()
+            .add_systems(Update, change_settings_system);
+
+        #[cfg(all(feature = "storage", not(target_arch = "wasm32")))]
+        {
+            use crate::options::settings::{
+                load_settings_system, save_settings_system,
+            };
+
+            app.add_systems(Startup, load_settings_system).add_systems(
+                Update,
+                save_settings_system
+                    .after(change_settings_system)
+                    .run_if(on_event::<ChangeSettingsEvent>),
+            );
+        }
     }
 }
@@ -0,0 +1,54 @@
+use bevy::prelude::*;
+use engine::settings::SettingsResource;
+
+/// Requests a change of the settings. The new settings are applied and, with
+/// the `storage` feature, saved right away.
+#[derive(Event, Clone, Debug)]
+pub struct ChangeSettingsEvent(pub SettingsResource);
+
+/// Applies the settings requested through [`ChangeSettingsEvent`].
+pub fn change_settings_system(
+    mut change_events: EventReader<ChangeSettingsEvent>,
+    mut settings: ResMut<SettingsResource>,
+) {
+    if let Some(event) = change_events.read().last() {
+        *settings = event.0.clone();
+        info!("Settings changed: {:?}", *settings);
+    }
+}
+
+#[cfg(all(feature = "storage", not(target_arch = "wasm32")))]
+pub use persistence::{load_settings_system, save_settings_system};
+
+/// Saves the settings with the `storage` crate so they persist across runs.
+#[cfg(all(feature = "storage", not(target_arch = "wasm32")))]
+mod persistence {
+    use bevy::prelude::*;
+    use engine::settings::SettingsResource;
+    use storage::Storage;
+
+    /// Storage key of the settings.
+    const SETTINGS_KEY: &str = "settings";
+
+    /// Replaces the default settings with the saved ones, if any.
+    pub fn load_settings_system(mut settings: ResMut<SettingsResource>) {
+        let Some(storage) = Storage::default_location() else {
+            return;
+        };
+        match storage.load::<SettingsResource>(SETTINGS_KEY) {
+            Ok(Some(saved)) => *settings = saved,
+            Ok(None) => {},
+            Err(error) => error!("Failed to load settings: {error}"),
+        }
+    }
+
+    /// Writes the settings to storage.
+    pub fn save_settings_system(settings: Res<SettingsResource>) {
+        let Some(storage) = Storage::default_location() else {
+            return;
+        };
+        if let Err(error) = storage.save(SETTINGS_KEY, &*settings) {
+            error!("Failed to save settings: {error}");
+        }
+    }
+}
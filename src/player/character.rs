@@ -4,6 +4,7 @@ use serde::Deserialize;
 use engine::character::{Character, CharacterType};
 
 #[derive(Resource, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct CharactersResource {
     pub characters: HashMap<CharacterType, Character>,
 }
\ No newline at end of file
@@ -1,19 +1,19 @@
 use assets::player::shadow::PlayerShadowAssets;
-use bevy::hierarchy::{ChildBuild, ChildBuilder};
+use bevy::hierarchy::ChildBuilder;
 use bevy::log::tracing_subscriber::fmt::init;
 use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
-use engine::abilities::shadow_monarch::{
-    MonarchFormBundle, ShadowDashBundle, ShadowSummonBundle,
-};
 use engine::abilities::{
-    AbilitiesResource, SlotOneAbilityType, SlotThreeAbilityType,
-    SlotTwoAbilityType,
+    AbilitiesResource, AbilityId, AbilityRegistry, AbilitySlotIDComponent,
 };
+use engine::aim::AimComponent;
 use engine::animation::AnimationComponent;
 use engine::character::{Character, CharacterType};
+use engine::combat::{KnockbackResistanceComponent, ResistancesComponent};
 use engine::health::{HealthComponent, HealthRegainComponent};
 use engine::input::{InputsResource, PlayerAction};
+use engine::mana::{ManaComponent, ManaRegainComponent};
+use engine::melee::{HeavyAttackComponent, LightAttackComponent};
 use engine::player::{
     PlayerBundle, PlayerData, PlayerIDComponent, PlayerVelocityComponent,
     PlayersResource,
@@ -21,6 +21,8 @@ use engine::player::{
 use engine::states::animation::AnimationStateMachine;
 use engine::states::player::PlayerState;
 use engine::states::util::GameCleanup;
+use engine::passive::PassiveInventoryComponent;
+use engine::weapon::{WeaponInventoryComponent, WeaponsResource};
 use leafwing_input_manager::prelude::ActionState;
 use leafwing_input_manager::InputManagerBundle;
 
@@ -28,76 +30,6 @@ use crate::animation::animation::AnimationsResource;
 use crate::game::resources::GameResource;
 use crate::player::character::CharactersResource;
 
-trait PlayerAbilityChildBuilderExt {
-    fn spawn_slot_1_ability(
-        &mut self,
-        ability_res: &AbilitiesResource,
-        ability_type: Option<&SlotOneAbilityType>,
-    );
-
-    fn spawn_slot_2_ability(
-        &mut self,
-        ability_res: &AbilitiesResource,
-        ability_type: Option<&SlotTwoAbilityType>,
-    );
-
-    fn spawn_slot_3_ability(
-        &mut self,
-        ability_res: &AbilitiesResource,
-        ability_type: Option<&SlotThreeAbilityType>,
-    );
-}
-
-impl PlayerAbilityChildBuilderExt for ChildBuilder<'_> {
-    fn spawn_slot_1_ability(
-        &mut self,
-        abilities_res: &AbilitiesResource,
-        ability_type: Option<&SlotOneAbilityType>,
-    ) {
-        if let Some(ability_one) = ability_type {
-            match ability_one {
-                SlotOneAbilityType::ShadowSummon => {
-                    self.spawn(ShadowSummonBundle::from(
-                        &abilities_res.shadow_summon,
-                    ))
-                },
-            };
-        }
-    }
-
-    fn spawn_slot_2_ability(
-        &mut self,
-        abilities_res: &AbilitiesResource,
-        ability_type: Option<&SlotTwoAbilityType>,
-    ) {
-        if let Some(ability_two) = ability_type {
-            match ability_two {
-                SlotTwoAbilityType::ShadowDash => {
-                    self.spawn(ShadowDashBundle::from(
-                        &abilities_res.shadow_dash,
-                    ))
-                },
-            };
-        }
-    }
-
-    fn spawn_slot_3_ability(
-        &mut self,
-        abilities_res: &AbilitiesResource,
-        ability_type: Option<&SlotThreeAbilityType>,
-    ) {
-        if let Some(ability_three) = ability_type {
-            match ability_three {
-                SlotThreeAbilityType::MonarchForm => {
-                    self.spawn(MonarchFormBundle::from(
-                        &abilities_res.monarch_form,
-                    ))
-                },
-            };
-        }
-    }
-}
-
 fn prepare_player_character_data<'a>(
     players_res: &mut ResMut<PlayersResource>, /* Geçici olarak karakter seçimi burada */
     characters_res: &'a Res<CharactersResource>,
@@ -174,10 +106,13 @@ fn add_input_components(
     entity_commands: &mut EntityCommands,
     input_res: &Res<InputsResource>,
 ) {
-    entity_commands.insert(InputManagerBundle::<PlayerAction> {
-        action_state: ActionState::default(),
-        input_map: input_res.player_keyboard.clone(),
-    });
+    entity_commands.insert((
+        InputManagerBundle::<PlayerAction> {
+            action_state: ActionState::default(),
+            input_map: input_res.player_keyboard.clone(),
+        },
+        AimComponent::default(),
+    ));
 }
 
 fn add_gameplay_components(
@@ -187,30 +122,75 @@ fn add_gameplay_components(
     entity_commands.insert((
         HealthComponent::from(char_data),
         HealthRegainComponent::default(),
+        ResistancesComponent::from(char_data),
+        KnockbackResistanceComponent(char_data.knockback_resistance),
+        LightAttackComponent::from(char_data),
+        HeavyAttackComponent::from(char_data),
+        ManaComponent::from(char_data),
+        ManaRegainComponent::from(char_data),
     ));
 }
 
+fn add_weapon_inventory(
+    entity_commands: &mut EntityCommands,
+    weapons_res: &WeaponsResource,
+    game_parameters: &GameResource,
+    char_data: &Character,
+) {
+    let mut inventory = WeaponInventoryComponent::default();
+    for weapon in &char_data.starting_weapons {
+        let Some(data) = weapons_res.get(weapon) else {
+            error!("Starting weapon `{}` not found", weapon);
+            continue;
+        };
+        inventory.equip(
+            weapon.clone(),
+            data.clone(),
+            game_parameters.max_weapons,
+        );
+    }
+    entity_commands.insert((inventory, PassiveInventoryComponent::default()));
+}
+
 fn add_util_componenets(entity_commands: &mut EntityCommands) {
     entity_commands.insert(GameCleanup);
 }
 
 fn spawn_player_abilities(
     parent: &mut ChildBuilder,
+    ability_registry: &Res<AbilityRegistry>,
     abilities_res: &Res<AbilitiesResource>,
     char_data: &Character,
 ) {
-    parent.spawn_slot_1_ability(
-        abilities_res,
-        char_data.slot_1_ability.as_ref(),
-    );
-    parent.spawn_slot_2_ability(
-        abilities_res,
-        char_data.slot_2_ability.as_ref(),
-    );
-    parent.spawn_slot_3_ability(
-        abilities_res,
-        char_data.slot_3_ability.as_ref(),
-    );
+    let slots: [(AbilitySlotIDComponent, Option<&AbilityId>); 3] = [
+        (
+            AbilitySlotIDComponent::One,
+            char_data.slot_1_ability.as_ref(),
+        ),
+        (
+            AbilitySlotIDComponent::Two,
+            char_data.slot_2_ability.as_ref(),
+        ),
+        (
+            AbilitySlotIDComponent::Three,
+            char_data.slot_3_ability.as_ref(),
+        ),
+    ];
+
+    for (slot, ability_id) in slots {
+        let Some(ability_id) = ability_id else {
+            continue;
+        };
+
+        if let Err(e) = ability_registry.spawn_ability(
+            parent,
+            abilities_res,
+            ability_id,
+            slot,
+        ) {
+            error!("Failed to spawn ability for slot {:?}: {}", slot, e);
+        }
+    }
 }
 
 pub fn spawn_player_system(
@@ -222,6 +202,8 @@ pub fn spawn_player_system(
     animations_res: Res<AnimationsResource>,
     input_res: Res<InputsResource>,
     abilities_res: Res<AbilitiesResource>,
+    ability_registry: Res<AbilityRegistry>,
+    weapons_res: Res<WeaponsResource>,
 ) {
     let char_data = match prepare_player_character_data(
         &mut players_res,
@@ -265,10 +247,21 @@ pub fn spawn_player_system(
 
     add_input_components(&mut player_entity_commands, &input_res);
     add_gameplay_components(&mut player_entity_commands, char_data);
+    add_weapon_inventory(
+        &mut player_entity_commands,
+        &weapons_res,
+        &game_parameters,
+        char_data,
+    );
     add_util_componenets(&mut player_entity_commands);
 
     player_entity_commands.with_children(|parent| {
-        spawn_player_abilities(parent, &abilities_res, char_data);
+        spawn_player_abilities(
+            parent,
+            &ability_registry,
+            &abilities_res,
+            char_data,
+        );
     });
 
     info!(
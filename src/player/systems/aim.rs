@@ -0,0 +1,115 @@
+use bevy::prelude::*;
+use engine::aim::{AimComponent, AimMode};
+use engine::health::HealthComponent;
+use engine::player::PlayerComponent;
+use engine::settings::SettingsResource;
+use engine::spawnable::mob::EnemyComponent;
+
+use crate::camera::CursorWorldPosition;
+use crate::game::resources::GameResource;
+
+/// Updates the [`AimComponent`] of every player from the [`AimMode`] of the
+/// settings.
+///
+/// - `Mouse` aims at the cursor, or in the facing direction if there is no
+///   cursor.
+/// - `RightStick` aims along the right stick of the first gamepad, and keeps
+///   the last direction while the stick is at rest.
+/// - `AutoNearest` aims at the nearest living enemy within `auto_aim_range`,
+///   or in the facing direction if there is none.
+pub fn player_aim_system(
+    settings: Res<SettingsResource>,
+    game_parameters: Res<GameResource>,
+    cursor: CursorWorldPosition,
+    gamepad_query: Query<&Gamepad>,
+    mut player_query: Query<
+        (&Transform, &mut AimComponent),
+        With<PlayerComponent>,
+    >,
+    enemy_query: Query<(&Transform, &HealthComponent), With<EnemyComponent>>,
+) {
+    for (transform, mut aim) in player_query.iter_mut() {
+        let position = transform.translation.truncate();
+        let facing = Vec2::new(transform.scale.x.signum(), 0.0);
+
+        let direction = match settings.aim_mode {
+            AimMode::Mouse => cursor
+                .get()
+                .map(|cursor| cursor - position)
+                .filter(|direction| *direction != Vec2::ZERO)
+                .unwrap_or(facing),
+            AimMode::RightStick => gamepad_query
+                .iter()
+                .next()
+                .map(Gamepad::right_stick)
+                .filter(|stick| stick.length() > game_parameters.stick_deadzone)
+                .unwrap_or(Vec2::ZERO),
+            AimMode::AutoNearest => enemy_query
+                .iter()
+                .filter(|(_, health)| !health.is_dead())
+                .map(|(enemy_transform, _)| {
+                    enemy_transform.translation.truncate() - position
+                })
+                .filter(|offset| {
+                    offset.length() <= game_parameters.auto_aim_range
+                })
+                .min_by(|a, b| {
+                    a.length_squared().total_cmp(&b.length_squared())
+                })
+                .unwrap_or(facing),
+        };
+        aim.set(direction);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::data::GameData;
+
+    #[test]
+    fn auto_aim_targets_nearest_enemy_in_range() {
+        let mut app = App::new();
+        let game_parameters = GameData::load(None).unwrap().game_parameters;
+        app.insert_resource(SettingsResource {
+            aim_mode: AimMode::AutoNearest,
+            ..Default::default()
+        })
+        .insert_resource(game_parameters)
+        .add_systems(Update, player_aim_system);
+
+        let player = app
+            .world_mut()
+            .spawn((
+                PlayerComponent,
+                Transform::from_scale(Vec3::new(-1.0, 1.0, 1.0)),
+                AimComponent::default(),
+            ))
+            .id();
+        let aim = |app: &App| app.world().get::<AimComponent>(player).copied();
+
+        app.update();
+        assert_eq!(
+            aim(&app).unwrap().direction,
+            Vec2::NEG_X
+        );
+
+        for position in [(0.0, 300.0), (0.0, -100.0), (50.0, 0.0)] {
+            app.world_mut().spawn((
+                EnemyComponent,
+                HealthComponent::new(10),
+                Transform::from_xyz(position.0, position.1, 0.0),
+            ));
+        }
+        let mut dead = HealthComponent::new(10);
+        dead.take_damage(10);
+        app.world_mut().spawn((
+            EnemyComponent,
+            dead,
+            Transform::from_xyz(10.0, 0.0, 0.0),
+        ));
+
+        app.update();
+        assert_eq!(aim(&app).unwrap().direction, Vec2::X);
+    }
+}
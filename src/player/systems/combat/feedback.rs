@@ -0,0 +1,205 @@
+use std::collections::HashSet;
+
+use bevy::prelude::*;
+use engine::combat::DamageAppliedEvent;
+use engine::player::PlayerComponent;
+use engine::settings::SettingsResource;
+
+use crate::camera::shake::CameraShakeComponent;
+use crate::camera::GameCamera;
+
+/// Tints the sprite of a hit entity until `timer` finishes.
+#[derive(Component, Debug)]
+pub struct HitFlashComponent {
+    pub timer: Timer,
+    /// Color of the sprite before the flash, restored once it ends.
+    pub original_color: Color,
+}
+
+/// Freeze of the game after a heavy hit.
+#[derive(Resource, Debug, Default)]
+pub struct HitStopResource {
+    /// Frames left before the game resumes.
+    pub frames_left: u32,
+}
+
+/// Gives feedback for every [`DamageAppliedEvent`], as set in the
+/// `hit_feedback` settings:
+///
+/// - the target's sprite flashes with `flash_color`,
+/// - a heavy hit (critical, or dealing at least `heavy_hit_damage`) freezes
+///   the game for `hit_stop_frames`,
+/// - the [`GameCamera`] gains trauma, more so when a player gets hit.
+pub fn hit_feedback_system(
+    mut commands: Commands,
+    mut damage_applied_events: EventReader<DamageAppliedEvent>,
+    settings: Res<SettingsResource>,
+    mut hit_stop: ResMut<HitStopResource>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+    mut target_query: Query<(
+        &mut Sprite,
+        Option<&mut HitFlashComponent>,
+        Has<PlayerComponent>,
+    )>,
+    mut camera_query: Query<&mut CameraShakeComponent, With<GameCamera>>,
+) {
+    let feedback = &settings.hit_feedback;
+    let mut flashed = HashSet::new();
+
+    for event in damage_applied_events.read() {
+        if event.damage == 0 {
+            continue;
+        }
+        let Ok((mut sprite, flash, is_player)) =
+            target_query.get_mut(event.target)
+        else {
+            continue;
+        };
+
+        if feedback.flash && flashed.insert(event.target) {
+            let timer =
+                Timer::from_seconds(feedback.flash_duration, TimerMode::Once);
+            match flash {
+                Some(mut flash) => flash.timer = timer,
+                None => {
+                    commands.entity(event.target).insert(HitFlashComponent {
+                        timer,
+                        original_color: sprite.color,
+                    });
+                },
+            }
+            sprite.color = feedback.flash_color;
+        }
+
+        if feedback.hit_stop
+            && (event.is_critical || event.damage >= feedback.heavy_hit_damage)
+        {
+            hit_stop.frames_left =
+                hit_stop.frames_left.max(feedback.hit_stop_frames);
+            if hit_stop.frames_left > 0 {
+                virtual_time.pause();
+            }
+        }
+
+        if feedback.screen_shake {
+            let trauma = if is_player {
+                feedback.player_hit_trauma
+            } else {
+                event.damage as f32 * feedback.trauma_per_damage
+            };
+            for mut shake in camera_query.iter_mut() {
+                shake.add_trauma(trauma);
+            }
+        }
+    }
+}
+
+/// Restores the color of flashing sprites once their flash ends.
+pub fn hit_flash_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut flash_query: Query<(
+        Entity,
+        &mut Sprite,
+        &mut HitFlashComponent,
+    )>,
+) {
+    for (entity, mut sprite, mut flash) in flash_query.iter_mut() {
+        if flash.timer.tick(time.delta()).finished() {
+            sprite.color = flash.original_color;
+            commands.entity(entity).remove::<HitFlashComponent>();
+        }
+    }
+}
+
+/// Counts down the frames of a hit-stop and resumes the game once they are
+/// over. Runs every frame, even while the game time is paused.
+pub fn hit_stop_system(
+    mut hit_stop: ResMut<HitStopResource>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+) {
+    if hit_stop.frames_left == 0 {
+        return;
+    }
+    hit_stop.frames_left -= 1;
+    if hit_stop.frames_left == 0 {
+        virtual_time.unpause();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use engine::combat::DamageType;
+
+    use super::*;
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.init_resource::<Time>()
+            .init_resource::<Time<Virtual>>()
+            .init_resource::<SettingsResource>()
+            .init_resource::<HitStopResource>()
+            .add_event::<DamageAppliedEvent>()
+            .add_systems(First, hit_stop_system)
+            .add_systems(
+                Update,
+                (hit_feedback_system, hit_flash_system).chain(),
+            );
+        app
+    }
+
+    fn hit(app: &mut App, target: Entity, damage: u32) {
+        app.world_mut().send_event(DamageAppliedEvent {
+            target,
+            damage,
+            damage_type: DamageType::Physical,
+            is_critical: false,
+            source: None,
+        });
+    }
+
+    #[test]
+    fn hit_flashes_target_and_restores_its_color() {
+        let mut app = app();
+        let color = Color::srgb(0.8, 0.2, 0.2);
+        let enemy = app.world_mut().spawn(Sprite { color, ..default() }).id();
+        let sprite_color =
+            |app: &App| app.world().get::<Sprite>(enemy).unwrap().color;
+
+        hit(&mut app, enemy, 1);
+        hit(&mut app, enemy, 1);
+        app.update();
+        let flash_color = SettingsResource::default().hit_feedback.flash_color;
+        assert_eq!(sprite_color(&app), flash_color);
+
+        app.world_mut()
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_secs(1));
+        app.update();
+        assert_eq!(sprite_color(&app), color);
+        assert!(app.world().get::<HitFlashComponent>(enemy).is_none());
+    }
+
+    #[test]
+    fn heavy_hit_pauses_game_for_hit_stop_frames() {
+        let mut app = app();
+        let enemy = app.world_mut().spawn(Sprite::default()).id();
+        let frames = SettingsResource::default().hit_feedback.hit_stop_frames;
+        let paused =
+            |app: &App| app.world().resource::<Time<Virtual>>().is_paused();
+
+        hit(&mut app, enemy, 1);
+        app.update();
+        assert!(!paused(&app));
+
+        hit(&mut app, enemy, 1000);
+        app.update();
+        for _ in 0..frames {
+            assert!(paused(&app));
+            app.update();
+        }
+        assert!(!paused(&app));
+    }
+}
@@ -0,0 +1,245 @@
+use bevy::prelude::*;
+use engine::aim::AimComponent;
+use engine::events::action::HeavyAttackEvent;
+use engine::events::animation::AnimationFrameEvent;
+use engine::melee::{
+    HeavyAttackComponent, MeleeHitboxComponent, HITBOX_FRAME_TAG,
+};
+use engine::player::{PlayerComponent, PlayerOutgoingDamageComponent};
+use engine::states::player::PlayerState;
+use engine::states::util::GameCleanup;
+
+use crate::animation::animation::AnimationsResource;
+
+/// Grows the charge of the heavy attack while the player is in
+/// [`PlayerState::Charging`] and releases it on [`HeavyAttackEvent`].
+pub fn heavy_attack_charge_system(
+    time: Res<Time>,
+    mut heavy_attack_events: EventReader<HeavyAttackEvent>,
+    mut player_query: Query<
+        (&PlayerState, &mut HeavyAttackComponent),
+        With<PlayerComponent>,
+    >,
+) {
+    for (player_state, mut heavy_attack) in player_query.iter_mut() {
+        if *player_state == PlayerState::Charging {
+            heavy_attack.add_charge(time.delta_secs());
+        }
+    }
+
+    for event in heavy_attack_events.read() {
+        if let Ok((_, mut heavy_attack)) = player_query.get_mut(event.0) {
+            heavy_attack.release();
+        }
+    }
+}
+
+/// Spawns the hitbox of a released heavy attack.
+///
+/// The hitbox is spawned on the [`HITBOX_FRAME_TAG`] frame event of the
+/// [`PlayerState::HeavyAttack`] clip that matches the first of the attack's
+/// `active_frames`. Its damage and range grow with the released charge. The
+/// release ends when its `release_timer` finishes.
+pub fn heavy_attack_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut frame_events: EventReader<AnimationFrameEvent>,
+    animations_res: Res<AnimationsResource>,
+    mut player_query: Query<
+        (
+            &AimComponent,
+            &mut HeavyAttackComponent,
+            &PlayerOutgoingDamageComponent,
+        ),
+        With<PlayerComponent>,
+    >,
+) {
+    for (_, mut heavy_attack, _) in player_query.iter_mut() {
+        if heavy_attack.is_releasing()
+            && heavy_attack.release_timer.tick(time.delta()).finished()
+        {
+            heavy_attack.released_charge = None;
+        }
+    }
+
+    for event in frame_events.read() {
+        if event.clip != PlayerState::HeavyAttack
+            || event.tag != HITBOX_FRAME_TAG
+        {
+            continue;
+        }
+        let Ok((aim, mut heavy_attack, outgoing_damage)) =
+            player_query.get_mut(event.entity)
+        else {
+            continue;
+        };
+        let Some(charge) = heavy_attack.released_charge else {
+            continue;
+        };
+        let (first, last) = heavy_attack.data.attack.active_frames;
+        if heavy_attack.hitbox_spawned || event.frame != first {
+            continue;
+        }
+        heavy_attack.hitbox_spawned = true;
+
+        let frame_duration = animations_res
+            .animations
+            .get(&PlayerState::HeavyAttack)
+            .map_or(0.1, |data| data.frame_duration);
+        let active_time =
+            (last + 1).saturating_sub(first).max(1) as f32 * frame_duration;
+
+        let attack = &heavy_attack.data.attack;
+        commands.spawn((
+            Name::new("Heavy Attack Hitbox"),
+            MeleeHitboxComponent {
+                owner: event.entity,
+                direction: aim.direction,
+                range: heavy_attack.range(charge),
+                half_arc: attack.arc * 0.5,
+                damage: (outgoing_damage.weapon_damage as f32
+                    * heavy_attack.damage_multiplier(charge))
+                .round() as u32,
+                damage_type: attack.damage_type,
+                knockback: attack.knockback * (1.0 + charge),
+                critical: outgoing_damage.critical,
+                lifetime: Timer::from_seconds(active_time, TimerMode::Once),
+                hit: Default::default(),
+            },
+            GameCleanup,
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use engine::character::CharacterType;
+
+    use super::*;
+    use crate::game::data::GameData;
+
+    fn app() -> (App, Entity) {
+        let data = GameData::load(None).unwrap();
+        let character =
+            &data.characters.characters[&CharacterType::ShadowMonarch];
+        let mut outgoing_damage =
+            PlayerOutgoingDamageComponent::from(character);
+        outgoing_damage.weapon_damage = 10;
+        outgoing_damage.critical = Default::default();
+
+        let mut app = App::new();
+        app.init_resource::<Time>()
+            .insert_resource(data.animations)
+            .add_event::<HeavyAttackEvent>()
+            .add_event::<AnimationFrameEvent>()
+            .add_systems(
+                Update,
+                (
+                    heavy_attack_charge_system,
+                    heavy_attack_system,
+                )
+                    .chain(),
+            );
+        let player = app
+            .world_mut()
+            .spawn((
+                PlayerComponent,
+                PlayerState::Idle,
+                AimComponent::default(),
+                HeavyAttackComponent::from(character),
+                outgoing_damage,
+            ))
+            .id();
+        (app, player)
+    }
+
+    fn advance(app: &mut App, seconds: f32) {
+        app.world_mut()
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_secs_f32(seconds));
+        app.update();
+    }
+
+    fn heavy_attack(app: &App, player: Entity) -> &HeavyAttackComponent {
+        app.world().get::<HeavyAttackComponent>(player).unwrap()
+    }
+
+    /// Releases the heavy attack at `charge` and returns the range and
+    /// damage of its hitbox.
+    fn release(app: &mut App, player: Entity, charge: f32) -> (f32, u32) {
+        let mut heavy_attack = app
+            .world_mut()
+            .get_mut::<HeavyAttackComponent>(player)
+            .unwrap();
+        heavy_attack.charge = charge;
+        let first = heavy_attack.data.attack.active_frames.0;
+        app.world_mut().send_event(HeavyAttackEvent(player));
+        app.update();
+        app.world_mut().send_event(AnimationFrameEvent {
+            entity: player,
+            clip: PlayerState::HeavyAttack,
+            frame: first,
+            tag: HITBOX_FRAME_TAG.to_string(),
+        });
+        app.update();
+
+        let mut query =
+            app.world_mut().query::<(Entity, &MeleeHitboxComponent)>();
+        let (hitbox, range, damage) = query
+            .iter(app.world())
+            .map(|(entity, hitbox)| (entity, hitbox.range, hitbox.damage))
+            .next()
+            .unwrap();
+        app.world_mut().despawn(hitbox);
+        (range, damage)
+    }
+
+    #[test]
+    fn charge_grows_while_held_up_to_full() {
+        let (mut app, player) = app();
+        let charge_time = heavy_attack(&app, player).data.charge_time;
+
+        advance(&mut app, charge_time * 0.5);
+        assert_eq!(heavy_attack(&app, player).charge, 0.0);
+
+        app.world_mut()
+            .entity_mut(player)
+            .insert(PlayerState::Charging);
+        advance(&mut app, charge_time * 0.5);
+        assert!((heavy_attack(&app, player).charge - 0.5).abs() < 1e-3);
+        advance(&mut app, charge_time);
+        assert_eq!(heavy_attack(&app, player).charge, 1.0);
+
+        app.world_mut().send_event(HeavyAttackEvent(player));
+        advance(&mut app, 0.0);
+        let heavy_attack = heavy_attack(&app, player);
+        assert_eq!(heavy_attack.released_charge, Some(1.0));
+        assert_eq!(heavy_attack.charge, 0.0);
+    }
+
+    #[test]
+    fn released_range_and_damage_scale_with_charge() {
+        let (mut app, player) = app();
+        let data = heavy_attack(&app, player).data.clone();
+        let damage = |multiplier: f32| (10.0 * multiplier).round() as u32;
+
+        assert_eq!(
+            release(&mut app, player, 0.0),
+            (
+                data.attack.range,
+                damage(data.attack.damage_multiplier)
+            )
+        );
+        assert_eq!(
+            release(&mut app, player, 1.0),
+            (
+                data.attack.range * data.max_range_multiplier,
+                damage(
+                    data.attack.damage_multiplier * data.max_damage_multiplier
+                )
+            )
+        );
+    }
+}
@@ -0,0 +1,251 @@
+use bevy::prelude::*;
+use engine::combat::{
+    DamageDealtEvent, HitStunComponent, KnockbackComponent, KnockbackEvent,
+    KnockbackResistanceComponent,
+};
+use engine::player::PlayerVelocityComponent;
+
+use crate::game::resources::GameResource;
+
+/// Knockback velocity below which a knocked back mob stops sliding.
+const KNOCKBACK_STOP_SPEED: f32 = 5.0;
+
+/// Turns hits with knockback into [`KnockbackEvent`]s pushing the target
+/// away from the entity that dealt the hit.
+pub fn damage_knockback_system(
+    mut damage_dealt_events: EventReader<DamageDealtEvent>,
+    mut knockback_events: EventWriter<KnockbackEvent>,
+    transform_query: Query<&Transform>,
+) {
+    for event in damage_dealt_events.read() {
+        if event.knockback <= 0.0 {
+            continue;
+        }
+        let Some(source) = event.source else {
+            continue;
+        };
+        let Ok([source_transform, target_transform]) =
+            transform_query.get_many([source, event.target])
+        else {
+            continue;
+        };
+
+        let direction = (target_transform.translation
+            - source_transform.translation)
+            .truncate()
+            .normalize_or_zero();
+        if direction == Vec2::ZERO {
+            continue;
+        }
+
+        knockback_events.send(KnockbackEvent {
+            target: event.target,
+            direction,
+            force: event.knockback,
+        });
+    }
+}
+
+/// Applies knockback and hit-stun, both reduced by the target's
+/// [`KnockbackResistanceComponent`].
+///
+/// Players get the push added to their [`PlayerVelocityComponent`], so the
+/// movement system decelerates them like any other movement. Other entities
+/// get a [`KnockbackComponent`] that [`knockback_movement_system`] resolves.
+pub fn knockback_system(
+    mut commands: Commands,
+    game_parameters: Res<GameResource>,
+    mut knockback_events: EventReader<KnockbackEvent>,
+    mut target_query: Query<(
+        Option<&KnockbackResistanceComponent>,
+        Option<&mut PlayerVelocityComponent>,
+        Option<&mut KnockbackComponent>,
+    )>,
+) {
+    for event in knockback_events.read() {
+        let Ok((resistance, player_velocity, knockback)) =
+            target_query.get_mut(event.target)
+        else {
+            continue;
+        };
+        let resistance = resistance.copied().unwrap_or_default();
+        let force = resistance.resolve(event.force);
+        if force <= 0.0 {
+            continue;
+        }
+        let impulse = event.direction * force;
+
+        if let Some(mut velocity) = player_velocity {
+            velocity.0 += impulse.x;
+            velocity.1 += impulse.y;
+        } else if let Some(mut knockback) = knockback {
+            knockback.velocity += impulse;
+        } else {
+            commands
+                .entity(event.target)
+                .insert(KnockbackComponent { velocity: impulse });
+        }
+
+        let stun = resistance.resolve(game_parameters.hit_stun_duration);
+        if stun > 0.0 {
+            commands
+                .entity(event.target)
+                .insert(HitStunComponent::new(stun));
+        }
+    }
+}
+
+/// Moves knocked back entities and lets their knockback velocity decay.
+pub fn knockback_movement_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    game_parameters: Res<GameResource>,
+    mut query: Query<(
+        Entity,
+        &mut Transform,
+        &mut KnockbackComponent,
+    )>,
+) {
+    let decay = (-game_parameters.knockback_friction * time.delta_secs()).exp();
+    for (entity, mut transform, mut knockback) in query.iter_mut() {
+        transform.translation +=
+            (knockback.velocity * time.delta_secs()).extend(0.0);
+        knockback.velocity *= decay;
+
+        if knockback.velocity.length() < KNOCKBACK_STOP_SPEED {
+            commands.entity(entity).remove::<KnockbackComponent>();
+        }
+    }
+}
+
+/// Ends hit-stuns whose timer ran out.
+pub fn hit_stun_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut HitStunComponent)>,
+) {
+    for (entity, mut hit_stun) in query.iter_mut() {
+        if hit_stun.timer.tick(time.delta()).finished() {
+            commands.entity(entity).remove::<HitStunComponent>();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use engine::combat::{CriticalStats, DamageType};
+    use engine::spawnable::mob::shadow::{
+        ShadowSoldierComponent, ShadowTargetPolicy,
+    };
+    use engine::spawnable::mob::AllyMobType;
+
+    use super::*;
+    use crate::game::data::GameData;
+    use crate::spawnable::mob::shadow::shadow_movement_system;
+
+    fn app() -> App {
+        let data = GameData::load(None).unwrap();
+        let mut app = App::new();
+        app.init_resource::<Time>()
+            .insert_resource(data.game_parameters)
+            .add_event::<KnockbackEvent>()
+            .add_systems(
+                Update,
+                (knockback_system, hit_stun_system),
+            );
+        app
+    }
+
+    fn advance(app: &mut App, seconds: f32) {
+        app.world_mut()
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_secs_f32(seconds));
+        app.update();
+    }
+
+    #[test]
+    fn resistance_scales_knockback_and_hit_stun() {
+        let mut app = app();
+        let hit_stun_duration =
+            app.world().resource::<GameResource>().hit_stun_duration;
+        let mob = app.world_mut().spawn_empty().id();
+        let boss = app
+            .world_mut()
+            .spawn(KnockbackResistanceComponent(0.9))
+            .id();
+        for target in [mob, boss] {
+            app.world_mut().send_event(KnockbackEvent {
+                target,
+                direction: Vec2::X,
+                force: 100.0,
+            });
+        }
+        app.update();
+
+        let world = app.world();
+        let velocity =
+            |entity| world.get::<KnockbackComponent>(entity).unwrap().velocity;
+        let stun = |entity: Entity| {
+            world
+                .get::<HitStunComponent>(entity)
+                .unwrap()
+                .timer
+                .duration()
+                .as_secs_f32()
+        };
+        assert_eq!(velocity(mob), Vec2::new(100.0, 0.0));
+        assert!((velocity(boss).x - 10.0).abs() < 1e-3);
+        assert_eq!(stun(mob), hit_stun_duration);
+        assert!((stun(boss) - hit_stun_duration * 0.1).abs() < 1e-3);
+    }
+
+    #[test]
+    fn hit_stun_suspends_mob_behavior_until_it_expires() {
+        let mut app = app();
+        app.add_systems(
+            Update,
+            shadow_movement_system.after(hit_stun_system),
+        );
+        let summoner = app.world_mut().spawn(Transform::default()).id();
+        // Far past its leash, so the shadow runs back as soon as it can
+        let shadow = app
+            .world_mut()
+            .spawn((
+                ShadowSoldierComponent {
+                    mob_type: AllyMobType::ShadowKnight,
+                    summoned_by: summoner,
+                    max_radius: 100.0,
+                    speed: 100.0,
+                    attack_range: 10.0,
+                    damage: 5,
+                    damage_type: DamageType::Physical,
+                    critical: CriticalStats::default(),
+                    attack_timer: Timer::from_seconds(1.0, TimerMode::Once),
+                    on_hit_status_effect: None,
+                    knockback: 0.0,
+                    target_policy: ShadowTargetPolicy::Nearest,
+                    target: None,
+                    leashing: false,
+                    lifetime: None,
+                },
+                Transform::from_xyz(200.0, 0.0, 0.0),
+                HitStunComponent::new(0.5),
+            ))
+            .id();
+        let x = |app: &App| {
+            app.world().get::<Transform>(shadow).unwrap().translation.x
+        };
+
+        advance(&mut app, 0.3);
+        assert_eq!(x(&app), 200.0);
+        assert!(app.world().get::<HitStunComponent>(shadow).is_some());
+
+        // The stun ends this frame, the shadow moves again on the next one
+        advance(&mut app, 0.3);
+        assert!(app.world().get::<HitStunComponent>(shadow).is_none());
+        advance(&mut app, 0.1);
+        assert!(x(&app) < 200.0);
+    }
+}
@@ -1,37 +1,289 @@
-use bevy::log::{debug, info};
-use bevy::prelude::{Entity, EventWriter, Query, Res, Time, Transform, With};
-use engine::input::PlayerAction;
-use engine::player::{
-    PlayerComponent, PlayerMobilityComponent, PlayerVelocityComponent,
+use assets::player::shadow::PlayerShadowAssets;
+use bevy::prelude::*;
+use engine::aim::AimComponent;
+use engine::combat::{CombatRngResource, DamageAppliedEvent, DamageDealtEvent};
+use engine::events::animation::AnimationFrameEvent;
+use engine::health::HealthComponent;
+use engine::melee::{
+    LightAttackComponent, MeleeHitboxComponent, HITBOX_FRAME_TAG,
+    SWING_FRAME_TAG,
 };
-use leafwing_input_manager::action_state::ActionState;
-use engine::events::animation::AnimationChangeEvent;
+use engine::player::{PlayerComponent, PlayerOutgoingDamageComponent};
+use engine::spawnable::mob::EnemyComponent;
 use engine::states::player::PlayerState;
-use crate::game::resources::GameResource;
+use engine::states::util::GameCleanup;
+
+use crate::animation::animation::AnimationsResource;
+
+/// Advances the light attack combo and spawns the hitbox of each swing.
+///
+/// While the player is in [`PlayerState::LightAttack`], every loop of the
+/// clip is one swing, started by its [`SWING_FRAME_TAG`] frame event. When a
+/// swing ends the combo moves to its next step and the clip is swapped for
+/// that step's sheet. Once the player stops attacking, the combo starts over
+/// if no light attack follows within `combo_window`.
+///
+/// The hitbox is spawned on the [`HITBOX_FRAME_TAG`] frame event of the
+/// first of the step's `active_frames` and lasts until the last one ends. It
+/// is aimed along the player's [`AimComponent`].
 pub fn light_attack_system(
+    mut commands: Commands,
     time: Res<Time>,
-    game_parameters: Res<GameResource>,
+    mut frame_events: EventReader<AnimationFrameEvent>,
+    animations_res: Res<AnimationsResource>,
+    player_assets: Res<PlayerShadowAssets>,
     mut player_query: Query<
         (
-            Entity,
-            &PlayerMobilityComponent,
-            &mut Transform,
-            &ActionState<PlayerAction>,
+            &AimComponent,
             &PlayerState,
-            &mut PlayerVelocityComponent,
+            &mut Sprite,
+            &mut LightAttackComponent,
+            &PlayerOutgoingDamageComponent,
         ),
         With<PlayerComponent>,
     >,
 ) {
-    for (
-        entity,
-        _player_mobility,
-        _mut_transform,
-        action_state,
-        current_animation_state,
-        _mut_player_velocity,
-    ) in player_query.iter_mut()
-    {
-        debug!("{:?}", action_state);
+    for (_, player_state, _, mut light_attack, _) in player_query.iter_mut() {
+        if *player_state != PlayerState::LightAttack {
+            light_attack.stop();
+            light_attack.tick_combo(time.delta());
+        }
+    }
+
+    for event in frame_events.read() {
+        if event.clip != PlayerState::LightAttack {
+            continue;
+        }
+        let Ok((
+            aim,
+            player_state,
+            mut sprite,
+            mut light_attack,
+            outgoing_damage,
+        )) = player_query.get_mut(event.entity)
+        else {
+            continue;
+        };
+        if *player_state != PlayerState::LightAttack {
+            continue;
+        }
+
+        match event.tag.as_str() {
+            SWING_FRAME_TAG if light_attack.swing_step.is_some() => {
+                // The clip looped, the swing is over and the next step starts
+                light_attack.finish_swing();
+                light_attack.start_swing();
+
+                let (image, layout) =
+                    player_assets.light_attack_clip(light_attack.step);
+                sprite.image = image;
+                sprite.texture_atlas = Some(TextureAtlas::from(layout));
+            },
+            SWING_FRAME_TAG => light_attack.start_swing(),
+            HITBOX_FRAME_TAG => {
+                let (first, last) = light_attack.attack().active_frames;
+                if light_attack.swing_step.is_none()
+                    || light_attack.hitbox_spawned
+                    || event.frame != first
+                {
+                    continue;
+                }
+                light_attack.hitbox_spawned = true;
+
+                let frame_duration = animations_res
+                    .animations
+                    .get(&PlayerState::LightAttack)
+                    .map_or(0.1, |data| data.frame_duration);
+                let active_time = (last + 1 - first) as f32 * frame_duration;
+
+                let attack = light_attack.attack();
+                commands.spawn((
+                    Name::new("Light Attack Hitbox"),
+                    MeleeHitboxComponent {
+                        owner: event.entity,
+                        direction: aim.direction,
+                        range: attack.range,
+                        half_arc: attack.arc * 0.5,
+                        damage: (outgoing_damage.weapon_damage as f32
+                            * attack.damage_multiplier)
+                            .round() as u32,
+                        damage_type: attack.damage_type,
+                        knockback: attack.knockback,
+                        critical: outgoing_damage.critical,
+                        lifetime: Timer::from_seconds(
+                            active_time,
+                            TimerMode::Once,
+                        ),
+                        hit: Default::default(),
+                    },
+                    GameCleanup,
+                ));
+            },
+            _ => {},
+        }
+    }
+}
+
+/// Starts the light attack combo over whenever the attacker gets hit.
+pub fn light_attack_combo_reset_system(
+    mut damage_applied_events: EventReader<DamageAppliedEvent>,
+    mut light_attack_query: Query<&mut LightAttackComponent>,
+) {
+    for event in damage_applied_events.read() {
+        if let Ok(mut light_attack) = light_attack_query.get_mut(event.target) {
+            light_attack.reset_combo();
+        }
+    }
+}
+
+/// Hits every enemy inside an active melee hitbox once and despawns the
+/// hitbox when its lifetime ends. The arc is centered on the hitbox owner,
+/// so it follows the attacker.
+pub fn melee_hitbox_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut rng: ResMut<CombatRngResource>,
+    mut damage_dealt_events: EventWriter<DamageDealtEvent>,
+    mut hitbox_query: Query<(Entity, &mut MeleeHitboxComponent)>,
+    owner_query: Query<&Transform>,
+    enemy_query: Query<
+        (Entity, &Transform, &HealthComponent),
+        With<EnemyComponent>,
+    >,
+) {
+    for (entity, mut hitbox) in hitbox_query.iter_mut() {
+        let Ok(owner_transform) = owner_query.get(hitbox.owner) else {
+            commands.entity(entity).despawn();
+            continue;
+        };
+        let origin = owner_transform.translation.truncate();
+
+        for (target, target_transform, health) in enemy_query.iter() {
+            if health.is_dead()
+                || hitbox.hit.contains(&target)
+                || !hitbox.contains(
+                    origin,
+                    target_transform.translation.truncate(),
+                )
+            {
+                continue;
+            }
+            hitbox.hit.insert(target);
+
+            let roll = hitbox.critical.roll(hitbox.damage, &mut rng.0);
+            damage_dealt_events.send(DamageDealtEvent {
+                target,
+                damage: roll.damage,
+                damage_type: hitbox.damage_type,
+                is_critical: roll.is_critical,
+                source: Some(hitbox.owner),
+                status_effect: None,
+                knockback: hitbox.knockback,
+            });
+        }
+
+        if hitbox.lifetime.tick(time.delta()).finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use engine::character::CharacterType;
+    use engine::combat::{CriticalStats, DamageType};
+
+    use super::*;
+    use crate::game::data::GameData;
+
+    fn advance(app: &mut App, seconds: f32) -> Vec<Entity> {
+        app.world_mut()
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_secs_f32(seconds));
+        app.update();
+        app.world_mut()
+            .resource_mut::<Events<DamageDealtEvent>>()
+            .drain()
+            .map(|event| event.target)
+            .collect()
+    }
+
+    #[test]
+    fn hitbox_hits_each_enemy_once_per_swing() {
+        let mut app = App::new();
+        app.init_resource::<Time>()
+            .init_resource::<CombatRngResource>()
+            .add_event::<DamageDealtEvent>()
+            .add_systems(Update, melee_hitbox_system);
+        let player = app.world_mut().spawn(Transform::default()).id();
+        let mut spawn_enemy = |x: f32| {
+            app.world_mut()
+                .spawn((
+                    EnemyComponent,
+                    HealthComponent::new(100),
+                    Transform::from_xyz(x, 0.0, 0.0),
+                ))
+                .id()
+        };
+        let near = spawn_enemy(20.0);
+        let far = spawn_enemy(80.0);
+        let behind = spawn_enemy(-20.0);
+        let hitbox = app
+            .world_mut()
+            .spawn(MeleeHitboxComponent {
+                owner: player,
+                direction: Vec2::X,
+                range: 100.0,
+                half_arc: 1.0,
+                damage: 10,
+                damage_type: DamageType::Physical,
+                knockback: 0.0,
+                critical: CriticalStats::default(),
+                lifetime: Timer::from_seconds(0.3, TimerMode::Once),
+                hit: Default::default(),
+            })
+            .id();
+
+        let mut hits = advance(&mut app, 0.1);
+        hits.sort();
+        let mut expected = vec![near, far];
+        expected.sort();
+        assert_eq!(hits, expected);
+
+        // Enemies walking into the arc later are still hit
+        app.world_mut()
+            .entity_mut(behind)
+            .insert(Transform::from_xyz(50.0, 0.0, 0.0));
+        assert_eq!(advance(&mut app, 0.1), [behind]);
+        assert!(advance(&mut app, 0.15).is_empty());
+        assert!(app.world().get_entity(hitbox).is_err());
+    }
+
+    #[test]
+    fn getting_hit_starts_the_combo_over() {
+        let data = GameData::load(None).unwrap();
+        let character =
+            &data.characters.characters[&CharacterType::ShadowMonarch];
+        let mut light_attack = LightAttackComponent::from(character);
+        light_attack.step = 2;
+        let mut app = App::new();
+        app.add_event::<DamageAppliedEvent>()
+            .add_systems(Update, light_attack_combo_reset_system);
+        let player = app.world_mut().spawn(light_attack).id();
+
+        app.world_mut().send_event(DamageAppliedEvent {
+            target: player,
+            damage: 1,
+            damage_type: DamageType::Physical,
+            is_critical: false,
+            source: None,
+        });
+        app.update();
+
+        let light_attack =
+            app.world().get::<LightAttackComponent>(player).unwrap();
+        assert_eq!(light_attack.step, 0);
     }
 }
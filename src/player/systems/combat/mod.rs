@@ -1,27 +1,128 @@
 use bevy::prelude::*;
-use engine::combat::{DamageDealtEvent, HealthRegainResetEvent};
+use bevy::time::TimeSystem;
+use engine::combat::{
+    CombatRngResource, DamageAppliedEvent, DamageDealtEvent,
+    HealAppliedEvent, HealthRegainResetEvent, KnockbackEvent, ResistancesComponent,
+};
 use engine::health::{HealthComponent, HealthRegainComponent};
+use engine::mana::{ManaComponent, ManaRegainComponent};
+use engine::objective::PlayerDiedEvent;
 use engine::player::PlayerComponent;
+use engine::states::app::AppStates;
+use engine::status_effect::ApplyStatusEffectEvent;
 
+use crate::game::resources::GameResource;
+use crate::animation::animate_sprite_system;
+use crate::animation::player::controller::player_animation_controller_system;
+use crate::player::systems::combat::feedback::{
+    hit_feedback_system, hit_flash_system, hit_stop_system, HitStopResource,
+};
+use crate::player::systems::combat::heavy_attack::{
+    heavy_attack_charge_system, heavy_attack_system,
+};
+use crate::player::systems::combat::knockback::{
+    damage_knockback_system, hit_stun_system, knockback_movement_system,
+    knockback_system,
+};
+use crate::player::systems::combat::light_attack::{
+    light_attack_combo_reset_system, light_attack_system, melee_hitbox_system,
+};
+use crate::player::systems::combat::status_effect::{
+    apply_status_effects_system, tick_status_effects_system,
+};
+
+pub mod feedback;
+pub mod heavy_attack;
+pub mod knockback;
 pub mod light_attack;
+pub mod status_effect;
 
 pub struct CombatPlugin;
 
 impl Plugin for CombatPlugin {
     fn build(&self, app: &mut App) {
         app.add_event::<DamageDealtEvent>()
+            .add_event::<DamageAppliedEvent>()
+            .add_event::<HealAppliedEvent>()
             .add_event::<HealthRegainResetEvent>()
+            .add_event::<ApplyStatusEffectEvent>()
+            .add_event::<KnockbackEvent>()
+            .add_event::<PlayerDiedEvent>()
+            .init_resource::<HitStopResource>()
+            .add_systems(Startup, init_combat_rng_system)
+            .add_systems(First, hit_stop_system.after(TimeSystem))
             .add_systems(
                 Update,
                 (
                     damage_system,
                     regenerate_health_system,
                     reset_regenerate_health_system,
+                    regenerate_mana_system,
                 ),
+            )
+            .add_systems(
+                Update,
+                (
+                    apply_status_effects_system,
+                    tick_status_effects_system,
+                )
+                    .chain()
+                    .after(damage_system)
+                    .run_if(in_state(AppStates::InGame)),
+            )
+            .add_systems(
+                Update,
+                (
+                    light_attack_system,
+                    heavy_attack_charge_system,
+                    heavy_attack_system,
+                    melee_hitbox_system,
+                )
+                    .chain()
+                    .after(animate_sprite_system)
+                    .after(player_animation_controller_system)
+                    .before(damage_system)
+                    .run_if(in_state(AppStates::InGame)),
+            )
+            .add_systems(
+                Update,
+                (
+                    damage_knockback_system,
+                    knockback_system,
+                    knockback_movement_system,
+                    hit_stun_system,
+                )
+                    .chain()
+                    .run_if(in_state(AppStates::InGame)),
+            )
+            .add_systems(
+                Update,
+                light_attack_combo_reset_system
+                    .after(damage_system)
+                    .run_if(in_state(AppStates::InGame)),
+            )
+            .add_systems(
+                Update,
+                (hit_feedback_system, hit_flash_system)
+                    .chain()
+                    .after(damage_system)
+                    .run_if(in_state(AppStates::InGame)),
             );
     }
 }
 
+/// Creates the combat RNG, seeded from `rng_seed` in the game parameters so
+/// critical strikes and damage variance can be reproduced.
+fn init_combat_rng_system(
+    mut commands: Commands,
+    game_parameters: Res<GameResource>,
+) {
+    commands.insert_resource(match game_parameters.rng_seed {
+        Some(seed) => CombatRngResource::seeded(seed),
+        None => CombatRngResource::default(),
+    });
+}
+
 /// System that handles health regeneration over time for player entities only.
 ///
 /// This systems simulates a delayed regeneration mechanism, where a player begins
@@ -35,6 +136,7 @@ impl Plugin for CombatPlugin {
 ///
 /// When the delay timer completes, the interval timer starts ticking. Every time it finishes,
 /// a fixed amount of health is restored, as specified in the `HealthRegainComponent`.
+/// A [`HealAppliedEvent`] is sent for every amount restored.
 ///
 /// This systems assumes that the `HealthRegainComponent::reset()` method is called elsewhere (e.g., in the
 /// `damage_system`) to restart the delay timer whenever the player takes damage.
@@ -57,16 +159,41 @@ impl Plugin for CombatPlugin {
 /// ```
 fn regenerate_health_system(
     time: Res<Time>,
+    mut heal_events: EventWriter<HealAppliedEvent>,
     mut query: Query<
         (
+            Entity,
             &mut HealthComponent,
             &mut HealthRegainComponent,
         ),
         With<PlayerComponent>,
     >,
 ) {
-    for (mut health, mut regain) in query.iter_mut() {
-        regain.update(time.delta(), &mut health);
+    for (entity, mut health, mut regain) in query.iter_mut() {
+        let amount = regain.update(time.delta(), &mut health);
+        if amount > 0 {
+            heal_events.send(HealAppliedEvent {
+                target: entity,
+                amount,
+            });
+        }
+    }
+}
+
+/// System that regenerates mana over time for player entities only.
+///
+/// Works like [`regenerate_health_system`]: the delay timer of the
+/// [`ManaRegainComponent`] is reset whenever the player spends mana on an
+/// ability, and mana is restored every interval once the delay has passed.
+fn regenerate_mana_system(
+    time: Res<Time>,
+    mut query: Query<
+        (&mut ManaComponent, &mut ManaRegainComponent),
+        With<PlayerComponent>,
+    >,
+) {
+    for (mut mana, mut regain) in query.iter_mut() {
+        regain.update(time.delta(), &mut mana);
     }
 }
 
@@ -110,8 +237,13 @@ fn reset_regenerate_health_system(
 ///
 /// This systems listens to [`DamageDealtEvent`] events and, for each event:
 /// - Fetches the corresponding target entity using its [`Entity`] ID.
+/// - Reduces (or increases) the damage by the target's [`ResistancesComponent`], if any.
 /// - Applies damage to the entity's [`HealthComponent`] by calling `.take_damage()`.
-/// - Optionally, this is a good place to trigger visual/audio feedback effects (e.g., hit animations, particles, sound).
+/// - Reports the damage actually taken as a [`DamageAppliedEvent`].
+/// - Sends a [`PlayerDiedEvent`] when the hit kills a player.
+/// - Forwards the hit's status effect, if any, as an [`ApplyStatusEffectEvent`].
+/// - Hit feedback (flash, hit-stop, screen shake) reacts to the [`DamageAppliedEvent`] in
+///   [`hit_feedback_system`].
 ///
 /// ### Components required per target entity:
 /// - [`HealthComponent`] — stores the entity's health and applies the damage logic.
@@ -126,26 +258,91 @@ fn reset_regenerate_health_system(
 /// Player attacks enemy → emits DamageDealtEvent(target = enemy_entity, damage = 10)
 /// System reads event → applies 10 damage to enemy_entity's HealthComponent
 /// ```
-///
-/// ### TODO:
-/// - Trigger hit effects (e.g., particles, screen shake, sound) based on `Transform` position.
-/// -
 fn damage_system(
     time: Res<Time>,
     mut damage_dealt_events: EventReader<DamageDealtEvent>,
     mut health_regain_reset_events: EventWriter<HealthRegainResetEvent>,
-    mut query: Query<(Entity, &mut HealthComponent)>,
+    mut apply_status_effect_events: EventWriter<ApplyStatusEffectEvent>,
+    mut damage_applied_events: EventWriter<DamageAppliedEvent>,
+    mut player_died_events: EventWriter<PlayerDiedEvent>,
+    mut query: Query<(
+        Entity,
+        &mut HealthComponent,
+        Option<&ResistancesComponent>,
+        Has<PlayerComponent>,
+    )>,
 ) {
     for event in damage_dealt_events.read() {
-        if let Ok((_entity, mut health_component)) = query.get_mut(event.target)
+        if let Ok((_entity, mut health_component, resistances, is_player)) =
+            query.get_mut(event.target)
         {
-            health_component.take_damage(event.damage);
+            let damage = resistances.map_or(event.damage, |resistances| {
+                resistances.resolve(event.damage, event.damage_type)
+            });
+            let was_alive = !health_component.is_dead();
+            health_component.take_damage(damage);
+
+            if is_player && was_alive && health_component.is_dead() {
+                player_died_events.send(PlayerDiedEvent {
+                    player: event.target,
+                });
+            }
+
+            damage_applied_events.send(DamageAppliedEvent {
+                target: event.target,
+                damage,
+                damage_type: event.damage_type,
+                is_critical: event.is_critical,
+                source: event.source,
+            });
 
             health_regain_reset_events.send(HealthRegainResetEvent {
                 entity: event.target,
             });
 
-            // TODO: Add visual/audio feedback effect at the entity's position
+            if let Some(effect) = &event.status_effect {
+                apply_status_effect_events.send(ApplyStatusEffectEvent {
+                    target: event.target,
+                    effect: effect.clone(),
+                    source: event.source,
+                });
+            }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn advance(app: &mut App, seconds: f32) -> u32 {
+        app.world_mut()
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_secs_f32(seconds));
+        app.update();
+        let mut query = app.world_mut().query::<&ManaComponent>();
+        query.single(app.world()).current
+    }
+
+    #[test]
+    fn mana_regenerates_every_interval_after_the_delay() {
+        let mut app = App::new();
+        app.init_resource::<Time>()
+            .add_systems(Update, regenerate_mana_system);
+        app.world_mut().spawn((
+            PlayerComponent,
+            ManaComponent { current: 0, max: 2 },
+            ManaRegainComponent::default(),
+        ));
+
+        // Regeneration waits 1s, then restores 1 mana every 0.5s
+        assert_eq!(advance(&mut app, 0.9), 0);
+        assert_eq!(advance(&mut app, 0.6), 1);
+        assert_eq!(advance(&mut app, 0.25), 1);
+        assert_eq!(advance(&mut app, 0.25), 2);
+        // Mana never exceeds its maximum
+        assert_eq!(advance(&mut app, 0.5), 2);
+    }
+}
@@ -0,0 +1,69 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use engine::combat::DamageDealtEvent;
+use engine::status_effect::{
+    ApplyStatusEffectEvent, StatusEffectsComponent, StatusEffectsResource,
+};
+
+/// Applies requested status effects following their stacking rules.
+///
+/// Entities that have no [`StatusEffectsComponent`] yet get one holding the
+/// effects applied to them this frame.
+pub fn apply_status_effects_system(
+    mut commands: Commands,
+    mut apply_events: EventReader<ApplyStatusEffectEvent>,
+    status_effects_res: Res<StatusEffectsResource>,
+    mut query: Query<&mut StatusEffectsComponent>,
+) {
+    let mut new_components = HashMap::<Entity, StatusEffectsComponent>::new();
+
+    for event in apply_events.read() {
+        let Some(data) = status_effects_res.effects.get(&event.effect) else {
+            warn!("Unknown status effect {}", event.effect);
+            continue;
+        };
+
+        if let Ok(mut status_effects) = query.get_mut(event.target) {
+            status_effects.apply(&event.effect, data, event.source);
+        } else {
+            new_components.entry(event.target).or_default().apply(
+                &event.effect,
+                data,
+                event.source,
+            );
+        }
+    }
+
+    for (entity, status_effects) in new_components {
+        if let Some(mut entity_commands) = commands.get_entity(entity) {
+            entity_commands.insert(status_effects);
+        }
+    }
+}
+
+/// Advances status effects, removes expired ones and turns damage over time
+/// ticks into [`DamageDealtEvent`]s.
+pub fn tick_status_effects_system(
+    time: Res<Time>,
+    mut damage_dealt_events: EventWriter<DamageDealtEvent>,
+    mut query: Query<(Entity, &mut StatusEffectsComponent)>,
+) {
+    for (entity, mut status_effects) in query.iter_mut() {
+        if status_effects.is_empty() {
+            continue;
+        }
+
+        for tick in status_effects.tick(time.delta()) {
+            damage_dealt_events.send(DamageDealtEvent {
+                target: entity,
+                damage: tick.damage,
+                damage_type: tick.damage_type,
+                is_critical: false,
+                source: tick.source,
+                status_effect: None,
+                knockback: 0.0,
+            });
+        }
+    }
+}
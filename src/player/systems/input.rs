@@ -1,20 +1,26 @@
 use bevy::prelude::*;
 
 use engine::events::action::{
-    DashEvent, HeavyAttackEvent, LightAttackEvent, MoveEvent, UseSkillEvent,
+    DashEvent, FireEvent, HeavyAttackEvent, LightAttackEvent, MoveEvent,
+    UseSkillEvent,
 };
 use engine::events::animation::AnimationChangeEvent;
 use engine::input::PlayerAction;
+use engine::melee::HeavyAttackComponent;
 use engine::player::PlayerComponent;
 use engine::states::player::{try_set_player_state, PlayerState};
+use engine::status_effect::StatusEffectsComponent;
 use leafwing_input_manager::prelude::ActionState;
 
+#[allow(clippy::too_many_arguments)]
 pub fn player_input_router_system(
     mut action_state_query: Query<
         (
             Entity,
             &ActionState<PlayerAction>,
             Mut<PlayerState>,
+            Option<&HeavyAttackComponent>,
+            Option<&StatusEffectsComponent>,
         ),
         With<PlayerComponent>,
     >,
@@ -22,15 +28,24 @@ pub fn player_input_router_system(
     mut dash_event_writer: EventWriter<DashEvent>,
     mut light_attack_writer: EventWriter<LightAttackEvent>,
     mut heavy_attack_writer: EventWriter<HeavyAttackEvent>,
+    mut fire_writer: EventWriter<FireEvent>,
     mut skill_writer: EventWriter<UseSkillEvent>,
     mut animation_events: EventWriter<AnimationChangeEvent>,
 ) {
-    let Ok((entity, action_state, mut current_player_state)) =
-        action_state_query.get_single_mut()
+    let Ok((
+        entity,
+        action_state,
+        mut current_player_state,
+        heavy_attack,
+        status_effects,
+    )) = action_state_query.get_single_mut()
     else {
         error!("Player action state not found.");
         return;
     };
+    // Stuns block every attack and ability until they wear off
+    let stunned =
+        status_effects.is_some_and(StatusEffectsComponent::is_stunned);
 
     let mut direction = Vec2::ZERO;
     if action_state.pressed(&PlayerAction::MoveUp) {
@@ -46,21 +61,49 @@ pub fn player_input_router_system(
         direction.x -= 1.0;
     }
 
+    let skill_slots = [
+        (PlayerAction::SlotOneAbility, 1),
+        (PlayerAction::SlotTwoAbility, 2),
+        (PlayerAction::SlotThreeAbility, 3),
+    ];
+    for (action, slot) in skill_slots {
+        if !stunned && action_state.just_pressed(&action) {
+            skill_writer.send(UseSkillEvent { entity, slot });
+        }
+    }
+
+    if !stunned && action_state.just_pressed(&PlayerAction::Fire) {
+        fire_writer.send(FireEvent(entity));
+    }
+
+    // Sent every frame so that `movement_system` keeps integrating the
+    // player's velocity (deceleration, knockback) without input.
+    move_event_writer.send(MoveEvent {
+        entity,
+        direction: direction.normalize_or_zero(),
+    });
+
     let mut player_states = vec![];
     if direction.length_squared() > 0.0 {
         player_states.push(PlayerState::Running);
     }
-    if action_state.pressed(&PlayerAction::LightAttack) {
+    if !stunned && action_state.pressed(&PlayerAction::LightAttack) {
         player_states.push(PlayerState::LightAttack);
     }
+    if !stunned && action_state.pressed(&PlayerAction::HeavyAttack) {
+        player_states.push(PlayerState::Charging);
+    }
+    if !stunned && action_state.just_released(&PlayerAction::HeavyAttack) {
+        heavy_attack_writer.send(HeavyAttackEvent(entity));
+        player_states.push(PlayerState::HeavyAttack);
+    } else if heavy_attack.is_some_and(HeavyAttackComponent::is_releasing) {
+        player_states.push(PlayerState::HeavyAttack);
+    }
 
     // Not implemented
     // if action_state.just_pressed(&PlayerAction::Dash) {
     // player_states.push(PlayerState::Dashing);
     // }
-    // if action_state.just_pressed(&PlayerAction::HeavyAttack) {
-    // player_states.push(PlayerState::HeavyAttack);
-    // }
     // if action_state.just_pressed(&PlayerAction::SlotOneAbility) {
     // player_states.push(PlayerState::Casting);
     // }
@@ -90,22 +133,22 @@ pub fn player_input_router_system(
 
         match new_state {
             PlayerState::Running => {
-                move_event_writer.send(MoveEvent {
-                    entity,
-                    direction: direction.normalize_or_zero(),
-                });
+                debug!("Running mod");
             },
             PlayerState::LightAttack => {
                 light_attack_writer.send(LightAttackEvent(entity));
             },
             PlayerState::Idle => {
                 debug!("Idle mod");
-            }
+            },
+            PlayerState::Charging => {
+                debug!("Charging heavy attack");
+            },
+            PlayerState::HeavyAttack => {
+                debug!("Heavy attack released");
+            },
             /*
             Not implemented!
-            PlayerState::HeavyAttack => {
-                heavy_attack_writer.send(HeavyAttackEvent(entity));
-            }
             PlayerState::Dashing => {
                 dash_event_writer.send(DashEvent(entity));
             }
@@ -1,3 +1,4 @@
+pub mod aim;
 pub mod input;
 pub mod combat;
 pub mod movement;
\ No newline at end of file
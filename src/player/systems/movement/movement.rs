@@ -1,10 +1,13 @@
 use crate::game::resources::GameResource;
 use bevy::prelude::*;
+use engine::combat::HitStunComponent;
 use engine::events::action::MoveEvent;
+use engine::melee::HeavyAttackComponent;
 use engine::player::{
     PlayerComponent, PlayerMobilityComponent, PlayerVelocityComponent,
 };
 use engine::states::player::PlayerState;
+use engine::status_effect::StatusEffectsComponent;
 
 pub fn movement_system(
     time: Res<Time>,
@@ -16,9 +19,13 @@ pub fn movement_system(
             &mut Transform,
             &PlayerState,
             &mut PlayerVelocityComponent,
+            Option<&StatusEffectsComponent>,
+            Option<&HeavyAttackComponent>,
+            Has<HitStunComponent>,
         ),
         With<PlayerComponent>,
     >,
+    fear_source_query: Query<&Transform, Without<PlayerComponent>>,
 ) {
     for event in movement_events.read() {
         let direction = event.direction;
@@ -27,10 +34,43 @@ pub fn movement_system(
         if let Ok((
             player_mobility,
             mut transform,
-            _current_animation_state,
+            current_state,
             mut player_velocity,
+            status_effects,
+            heavy_attack,
+            stunned,
         )) = player_query.get_mut(event.entity)
         {
+            // Input is ignored while stunned, so knockback added to the
+            // velocity is only slowed down by deceleration
+            let stunned = stunned
+                || status_effects
+                    .is_some_and(StatusEffectsComponent::is_stunned);
+            // Fear takes over the input and runs away from its source
+            let fear_source = status_effects
+                .and_then(StatusEffectsComponent::fear_source)
+                .and_then(|source| fear_source_query.get(source).ok());
+            let direction = if stunned {
+                Vec2::ZERO
+            } else if let Some(source_transform) = fear_source {
+                (transform.translation - source_transform.translation)
+                    .truncate()
+                    .normalize_or_zero()
+            } else {
+                direction
+            };
+
+            // Slows and hastes scale the top speed
+            let mut speed = player_mobility.speed
+                * status_effects
+                    .map_or(1.0, StatusEffectsComponent::movement_multiplier);
+
+            // Charging a heavy attack slows the player down
+            if *current_state == PlayerState::Charging {
+                speed *= heavy_attack
+                    .map_or(1.0, |heavy| heavy.data.charging_speed_multiplier);
+            }
+
             let x_axis = direction.x.round() as i8;
             let y_axis = direction.y.round() as i8;
 
@@ -50,7 +90,7 @@ pub fn movement_system(
                 &mut player_velocity.0,
                 player_mobility.acceleration.x,
                 player_mobility.deceleration.x,
-                player_mobility.speed.x,
+                speed.x,
                 game_parameters.stop_threshold,
             );
 
@@ -59,7 +99,7 @@ pub fn movement_system(
                 &mut player_velocity.1,
                 player_mobility.acceleration.y,
                 player_mobility.deceleration.y,
-                player_mobility.speed.y,
+                speed.y,
                 game_parameters.stop_threshold,
             );
 
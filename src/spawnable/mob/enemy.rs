@@ -0,0 +1,322 @@
+use assets::player::shadow::PlayerShadowAssets;
+use assets::spawnable::projectile::ProjectileAssets;
+use bevy::prelude::*;
+use engine::animation::AnimationComponent;
+use engine::combat::{CriticalStats, HitStunComponent};
+use engine::health::HealthComponent;
+use engine::objective::EnemyDefeatedEvent;
+use engine::spawnable::mob::{
+    EnemyComponent, EnemyTypeComponent, MobStatsResource, MobType,
+    RangedAttackComponent, SpawnMobEvent,
+};
+use engine::spawnable::projectile::behavior::ProjectilesResource;
+use engine::spawnable::projectile::{
+    spread_directions, ProjectileComponent, ProjectileType,
+    PROJECTILE_HIT_RADIUS,
+};
+use engine::spawnable::Faction;
+use engine::states::player::PlayerState;
+use engine::states::util::GameCleanup;
+use engine::status_effect::StatusEffectsComponent;
+
+use crate::animation::animation::AnimationsResource;
+use crate::game::resources::GameResource;
+use crate::spawnable::projectile::systems::spawn::spawn_projectile;
+
+/// Tint applied to the player's sprite to draw enemies until they have
+/// sprites of their own.
+// TODO: replace with enemy sprite sheets and animations per `EnemyMobType`.
+const ENEMY_COLOR: Color = Color::srgba(0.8, 0.2, 0.2, 1.0);
+
+/// Spawns enemies requested through [`SpawnMobEvent`] with the stats of
+/// their type from `mob_stats.ron`. Enemies with a ranged attack get a
+/// [`RangedAttackComponent`].
+pub fn spawn_enemy_system(
+    mut commands: Commands,
+    mut spawn_mob_events: EventReader<SpawnMobEvent>,
+    mob_stats_res: Res<MobStatsResource>,
+    player_assets: Res<PlayerShadowAssets>,
+    animations_res: Res<AnimationsResource>,
+) {
+    for event in spawn_mob_events.read() {
+        let MobType::Enemy(mob_type) = event.mob_type else {
+            continue;
+        };
+        let mob_stats =
+            mob_stats_res.enemy(mob_type).cloned().unwrap_or_default();
+        let ranged_attack = mob_stats.ranged_attack();
+
+        let mut entity_commands = commands.spawn((
+            Name::new(mob_type.to_string()),
+            EnemyComponent,
+            EnemyTypeComponent {
+                mob_type,
+                boss: event.boss,
+            },
+            HealthComponent::new(mob_stats.max_health),
+            mob_stats.knockback_resistance(event.boss),
+            mob_stats.resistances,
+            Transform {
+                translation: event.position.extend(0.0),
+                rotation: event.rotation,
+                ..default()
+            },
+            Sprite {
+                color: ENEMY_COLOR,
+                ..Sprite::from_atlas_image(
+                    player_assets.idle_image.clone(),
+                    TextureAtlas::from(player_assets.idle_layout.clone()),
+                )
+            },
+            GameCleanup,
+        ));
+
+        if let Some(ranged_attack) = ranged_attack {
+            entity_commands.insert(ranged_attack);
+        }
+        if let Some(animation_data) =
+            animations_res.animations.get(&PlayerState::Idle)
+        {
+            entity_commands.insert(AnimationComponent::from(animation_data));
+        }
+    }
+}
+
+/// Despawns dead enemies and reports each of them as an
+/// [`EnemyDefeatedEvent`].
+pub fn enemy_death_system(
+    mut commands: Commands,
+    mut defeated_events: EventWriter<EnemyDefeatedEvent>,
+    enemy_query: Query<
+        (
+            Entity,
+            &EnemyTypeComponent,
+            &HealthComponent,
+        ),
+        With<EnemyComponent>,
+    >,
+) {
+    for (entity, enemy_type, health) in enemy_query.iter() {
+        if !health.is_dead() {
+            continue;
+        }
+        defeated_events.send(EnemyDefeatedEvent {
+            is_boss: enemy_type.boss,
+            entity,
+            mob_type: enemy_type.mob_type,
+        });
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+/// Fires the ranged attack of every enemy at the nearest living player or
+/// summon within range, once every `fire_interval`.
+///
+/// The cooldown runs whether or not a target is in range, so an enemy that
+/// was ready fires as soon as a target comes close.
+///
+/// The shot is an enemy projectile, so it only hurts players and their
+/// summons. Stunned enemies hold their fire, whether the stun comes from a
+/// hit or from a status effect.
+pub fn enemy_ranged_attack_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    game_parameters: Res<GameResource>,
+    projectile_assets: Res<ProjectileAssets>,
+    projectiles_res: Res<ProjectilesResource>,
+    mut enemy_query: Query<
+        (
+            Entity,
+            &Transform,
+            &mut RangedAttackComponent,
+            Option<&StatusEffectsComponent>,
+        ),
+        (
+            With<EnemyComponent>,
+            Without<HitStunComponent>,
+        ),
+    >,
+    target_query: Query<
+        (&Transform, &HealthComponent),
+        Without<EnemyComponent>,
+    >,
+) {
+    for (entity, transform, mut ranged_attack, status_effects) in
+        enemy_query.iter_mut()
+    {
+        ranged_attack.fire_timer.tick(time.delta());
+        if status_effects.is_some_and(StatusEffectsComponent::is_stunned) {
+            continue;
+        }
+
+        let position = transform.translation.truncate();
+        let target = target_query
+            .iter()
+            .filter(|(_, health)| !health.is_dead())
+            .map(|(target_transform, _)| {
+                target_transform.translation.truncate()
+            })
+            .filter(|target| {
+                target.distance(position) <= ranged_attack.data.range
+            })
+            .min_by(|a, b| {
+                a.distance_squared(position)
+                    .total_cmp(&b.distance_squared(position))
+            });
+        let Some(target) = target else {
+            continue;
+        };
+        if !ranged_attack.fire_timer.finished() {
+            continue;
+        }
+        ranged_attack.fire_timer.reset();
+
+        let Some(projectile_data) =
+            projectiles_res.get(&ranged_attack.data.projectile)
+        else {
+            error!(
+                "Projectile kind `{}` not found",
+                ranged_attack.data.projectile
+            );
+            continue;
+        };
+        let direction = (target - position).normalize_or(Vec2::X);
+
+        for direction in spread_directions(
+            direction,
+            ranged_attack.data.projectile_count,
+            game_parameters.projectile_gap,
+            game_parameters.max_spread_arc,
+        ) {
+            spawn_projectile(
+                &mut commands,
+                &projectile_assets,
+                ProjectileComponent {
+                    projectile_type: ProjectileType::Bullet(Faction::Enemy),
+                    owner: entity,
+                    damage: ranged_attack.data.damage,
+                    damage_type: ranged_attack.damage_type,
+                    critical: CriticalStats::default(),
+                    velocity: direction * ranged_attack.data.projectile_speed,
+                    radius: PROJECTILE_HIT_RADIUS,
+                    lifetime: Timer::from_seconds(
+                        ranged_attack.data.projectile_lifetime,
+                        TimerMode::Once,
+                    ),
+                    hit: Default::default(),
+                },
+                projectile_data,
+                position.extend(transform.translation.z + 0.1),
+                1.0,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use engine::spawnable::mob::EnemyMobType;
+
+    use super::*;
+    use crate::game::data::GameData;
+
+    fn app() -> App {
+        let data = GameData::load(None).unwrap();
+        let mut app = App::new();
+        app.init_resource::<Time>()
+            .insert_resource(data.game_parameters)
+            .insert_resource(data.projectiles)
+            .insert_resource(ProjectileAssets {
+                ally_bullet_layout: Handle::default(),
+                ally_bullet_image: Handle::default(),
+                enemy_bullet_layout: Handle::default(),
+                enemy_bullet_image: Handle::default(),
+            })
+            .add_systems(Update, enemy_ranged_attack_system);
+        app
+    }
+
+    /// Advances time and returns how many projectiles were fired since the
+    /// last call.
+    fn advance(app: &mut App, seconds: f32) -> usize {
+        app.world_mut()
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_secs_f32(seconds));
+        app.update();
+        let mut query = app
+            .world_mut()
+            .query_filtered::<Entity, With<ProjectileComponent>>();
+        let projectiles: Vec<Entity> = query.iter(app.world()).collect();
+        for projectile in &projectiles {
+            app.world_mut().despawn(*projectile);
+        }
+        projectiles.len()
+    }
+
+    #[test]
+    fn fires_every_interval_and_as_soon_as_a_target_is_in_range() {
+        let mut app = app();
+        let ranged_attack = GameData::load(None)
+            .unwrap()
+            .mob_stats
+            .enemy(EnemyMobType::GoblinArcher)
+            .and_then(|stats| stats.ranged_attack())
+            .unwrap();
+        let fire_interval = ranged_attack.data.fire_interval;
+        let range = ranged_attack.data.range;
+        app.world_mut().spawn((
+            EnemyComponent,
+            Transform::default(),
+            ranged_attack,
+        ));
+        let target = app
+            .world_mut()
+            .spawn((
+                HealthComponent::new(100),
+                Transform::from_xyz(range * 0.5, 0.0, 0.0),
+            ))
+            .id();
+
+        assert_eq!(
+            advance(&mut app, fire_interval * 0.5),
+            0
+        );
+        assert_eq!(
+            advance(&mut app, fire_interval * 0.5),
+            1
+        );
+        assert_eq!(
+            advance(&mut app, fire_interval * 0.75),
+            0
+        );
+        assert_eq!(
+            advance(&mut app, fire_interval * 0.25),
+            1
+        );
+
+        app.world_mut()
+            .entity_mut(target)
+            .insert(Transform::from_xyz(
+                range * 2.0,
+                0.0,
+                0.0,
+            ));
+        assert_eq!(
+            advance(&mut app, fire_interval * 1.3),
+            0
+        );
+        app.world_mut()
+            .entity_mut(target)
+            .insert(Transform::from_xyz(
+                range * 0.5,
+                0.0,
+                0.0,
+            ));
+        assert_eq!(
+            advance(&mut app, fire_interval * 0.1),
+            1
+        );
+    }
+}
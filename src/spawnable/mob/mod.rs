@@ -0,0 +1,240 @@
+pub mod enemy;
+pub mod roster;
+pub mod shadow;
+pub mod spawner;
+
+use std::collections::{HashMap, HashSet};
+
+use assets::player::shadow::PlayerShadowAssets;
+use bevy::prelude::*;
+use engine::abilities::shadow_monarch::ShadowSummonComponent;
+use engine::animation::AnimationComponent;
+use engine::health::HealthComponent;
+use engine::objective::EnemyDefeatedEvent;
+use engine::player::PlayerOutgoingDamageComponent;
+use engine::spawnable::mob::shadow::{
+    ShadowArmyResource, ShadowRank, ShadowSoldierComponent,
+};
+use engine::spawnable::mob::{
+    AllyMobType, MobStatsResource, MobType, SpawnMobEvent,
+};
+use engine::states::app::AppStates;
+use engine::states::player::PlayerState;
+use engine::states::util::GameCleanup;
+
+use crate::animation::animation::AnimationsResource;
+use crate::spawnable::mob::enemy::{
+    enemy_death_system, enemy_ranged_attack_system, spawn_enemy_system,
+};
+use crate::spawnable::mob::roster::{
+    shadow_extraction_system, shadow_preference_input_system,
+    shadow_preference_system, ToggleShadowPreferenceEvent,
+};
+use crate::spawnable::mob::shadow::{
+    shadow_attack_system, shadow_lifetime_system, shadow_movement_system,
+    shadow_target_selection_system,
+};
+use crate::spawnable::mob::spawner::{
+    enemy_spawner_system, init_enemy_spawner_system,
+};
+
+/// Spawns mobs requested through [`SpawnMobEvent`] and runs their behavior.
+pub struct MobPlugin;
+
+impl Plugin for MobPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ToggleShadowPreferenceEvent>()
+            .add_event::<EnemyDefeatedEvent>()
+            .init_resource::<ShadowArmyResource>()
+            .add_systems(
+                Update,
+                (
+                    shadow_extraction_system,
+                    shadow_preference_input_system,
+                    shadow_preference_system,
+                )
+                    .chain(),
+            );
+
+        #[cfg(all(feature = "storage", not(target_arch = "wasm32")))]
+        {
+            use crate::spawnable::mob::roster::{
+                load_shadow_army_system, save_shadow_army_system,
+            };
+
+            app.add_systems(Startup, load_shadow_army_system)
+                .add_systems(OnExit(AppStates::InGame), save_shadow_army_system)
+                .add_systems(
+                    Last,
+                    save_shadow_army_system.run_if(on_event::<AppExit>),
+                );
+        }
+
+        app.add_systems(
+            Update,
+            (
+                spawn_shadow_soldier_system,
+                shadow_target_selection_system,
+                shadow_movement_system,
+                shadow_attack_system,
+                shadow_lifetime_system,
+            )
+                .chain()
+                .run_if(in_state(AppStates::InGame)),
+        )
+        .add_systems(OnEnter(AppStates::GameInit), init_enemy_spawner_system)
+        .add_systems(
+            Update,
+            (
+                enemy_spawner_system,
+                spawn_enemy_system,
+                enemy_ranged_attack_system,
+                enemy_death_system,
+            )
+                .chain()
+                .run_if(in_state(AppStates::InGame)),
+        );
+    }
+}
+
+/// Tint applied to the summoner's sprite to draw shadow soldiers.
+const SHADOW_SOLDIER_COLOR: Color = Color::srgba(0.3, 0.15, 0.5, 0.85);
+
+/// Scale of a shadow soldier relative to its summoner's sprite.
+const SHADOW_SOLDIER_SCALE: f32 = 0.75;
+
+/// Spawns allied shadow soldiers for `ShadowSummon` spawn requests.
+///
+/// Stats are derived from the summoner's `ShadowSummonComponent` (found among
+/// its ability children) and scaled from the summoner's own health and weapon
+/// damage, then multiplied by the shadow's rank in the army roster. Requests
+/// beyond `max_summons` active shadows are ignored, and a named shadow that is
+/// already fighting is replaced by a generic one.
+#[allow(clippy::too_many_arguments)]
+fn spawn_shadow_soldier_system(
+    mut commands: Commands,
+    mut spawn_mob_events: EventReader<SpawnMobEvent>,
+    army: Res<ShadowArmyResource>,
+    mob_stats_res: Res<MobStatsResource>,
+    player_assets: Res<PlayerShadowAssets>,
+    animations_res: Res<AnimationsResource>,
+    summoner_query: Query<(
+        &Children,
+        &Transform,
+        &HealthComponent,
+        &PlayerOutgoingDamageComponent,
+    )>,
+    summon_query: Query<&ShadowSummonComponent>,
+    soldier_query: Query<&ShadowSoldierComponent>,
+) {
+    let mut active_summons = HashMap::<Entity, u32>::new();
+    let mut active_named = HashSet::<AllyMobType>::new();
+    for soldier in soldier_query.iter() {
+        *active_summons.entry(soldier.summoned_by).or_default() += 1;
+        if army.get(soldier.mob_type).is_some_and(|record| record.unique) {
+            active_named.insert(soldier.mob_type);
+        }
+    }
+
+    for event in spawn_mob_events.read() {
+        let mut mob_type = match event.mob_type {
+            MobType::Ally(mob_type) if mob_type.is_shadow() => mob_type,
+            _ => continue,
+        };
+        let Some(summoner) = event.summoned_by else {
+            warn!("Shadow summon requested without a summoner");
+            continue;
+        };
+        let Ok((children, summoner_transform, health, outgoing_damage)) =
+            summoner_query.get(summoner)
+        else {
+            continue;
+        };
+        let Some(summon) = children
+            .iter()
+            .find_map(|&child| summon_query.get(child).ok())
+        else {
+            warn!("Summoner {:?} has no ShadowSummon ability", summoner);
+            continue;
+        };
+
+        let active = active_summons.entry(summoner).or_default();
+        if *active >= summon.max_summons {
+            continue;
+        }
+        *active += 1;
+
+        let mut record = army.get(mob_type);
+        if record.is_some_and(|record| record.unique) {
+            if !active_named.insert(mob_type) {
+                mob_type = AllyMobType::ShadowSummon;
+                record = None;
+            }
+        } else if record.is_none() {
+            mob_type = AllyMobType::ShadowSummon;
+        }
+        let name = record.map_or("Shadow Soldier", |record| &record.name);
+        let multiplier = record
+            .map_or(ShadowRank::Normal, |record| record.rank)
+            .stat_multiplier();
+
+        let max_health = ((health.max as f32
+            * summon.health_percentage
+            * multiplier)
+            .round() as u32)
+            .max(1);
+        let damage = (outgoing_damage.weapon_damage as f32
+            * summon.damage_percentage
+            * multiplier)
+            .round() as u32;
+        let mob_stats =
+            mob_stats_res.ally(mob_type).cloned().unwrap_or_default();
+        let scale = summoner_transform.scale.y.abs() * SHADOW_SOLDIER_SCALE;
+
+        let mut entity_commands = commands.spawn((
+            Name::new(name.to_string()),
+            ShadowSoldierComponent {
+                mob_type,
+                summoned_by: summoner,
+                max_radius: summon.max_radius,
+                speed: summon.move_speed,
+                attack_range: summon.attack_range,
+                damage,
+                damage_type: mob_stats.damage_type,
+                critical: outgoing_damage.critical,
+                attack_timer: Timer::from_seconds(
+                    summon.attack_interval,
+                    TimerMode::Once,
+                ),
+                on_hit_status_effect: summon.on_hit_status_effect.clone(),
+                knockback: summon.knockback,
+                target_policy: summon.target_policy,
+                target: None,
+                leashing: false,
+                lifetime: summon.lifetime.clone(),
+            },
+            HealthComponent::new(max_health),
+            mob_stats.knockback_resistance(event.boss),
+            mob_stats.resistances,
+            Transform {
+                translation: event.position.extend(0.0),
+                rotation: event.rotation,
+                scale: Vec3::new(scale, scale, 1.0),
+            },
+            Sprite {
+                color: SHADOW_SOLDIER_COLOR,
+                ..Sprite::from_atlas_image(
+                    player_assets.idle_image.clone(),
+                    TextureAtlas::from(player_assets.idle_layout.clone()),
+                )
+            },
+            GameCleanup,
+        ));
+
+        if let Some(animation_data) =
+            animations_res.animations.get(&PlayerState::Idle)
+        {
+            entity_commands.insert(AnimationComponent::from(animation_data));
+        }
+    }
+}
@@ -0,0 +1,182 @@
+use bevy::prelude::*;
+use engine::abilities::shadow_monarch::ShadowSummonComponent;
+use engine::input::PlayerAction;
+use engine::objective::EnemyDefeatedEvent;
+use engine::player::PlayerComponent;
+use engine::spawnable::mob::shadow::{
+    ShadowArmyResource, ShadowExtractionsResource,
+};
+use engine::spawnable::mob::AllyMobType;
+use leafwing_input_manager::prelude::ActionState;
+
+/// Requests a shadow type to be added to or removed from the shadows
+/// `ShadowSummon` prefers. Sent when the player presses
+/// `CycleShadowPreference`.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct ToggleShadowPreferenceEvent(pub AllyMobType);
+
+/// Extracts a shadow from every defeated enemy listed in the extraction
+/// table, as long as a player can summon shadows.
+pub fn shadow_extraction_system(
+    mut defeated_events: EventReader<EnemyDefeatedEvent>,
+    mut army: ResMut<ShadowArmyResource>,
+    extractions: Res<ShadowExtractionsResource>,
+    summon_query: Query<(), With<ShadowSummonComponent>>,
+) {
+    if summon_query.is_empty() {
+        defeated_events.clear();
+        return;
+    }
+
+    for event in defeated_events.read() {
+        let Some(extraction) = extractions.get(event.mob_type, event.is_boss)
+        else {
+            continue;
+        };
+        if army.extract(extraction) {
+            info!(
+                "Extracted shadow {} ({:?})",
+                extraction.name, extraction.rank
+            );
+        }
+    }
+}
+
+/// Cycles the preferred shadow when the player presses
+/// `CycleShadowPreference`.
+pub fn shadow_preference_input_system(
+    action_state_query: Query<
+        &ActionState<PlayerAction>,
+        With<PlayerComponent>,
+    >,
+    army: Res<ShadowArmyResource>,
+    mut toggle_events: EventWriter<ToggleShadowPreferenceEvent>,
+) {
+    let pressed = action_state_query.iter().any(|action_state| {
+        action_state.just_pressed(&PlayerAction::CycleShadowPreference)
+    });
+    if !pressed {
+        return;
+    }
+    for mob_type in army.next_preference_toggles() {
+        toggle_events.send(ToggleShadowPreferenceEvent(mob_type));
+    }
+}
+
+/// Applies preference changes requested by the player.
+pub fn shadow_preference_system(
+    mut toggle_events: EventReader<ToggleShadowPreferenceEvent>,
+    mut army: ResMut<ShadowArmyResource>,
+) {
+    for event in toggle_events.read() {
+        army.toggle_preferred(event.0);
+    }
+}
+
+#[cfg(all(feature = "storage", not(target_arch = "wasm32")))]
+pub use persistence::{load_shadow_army_system, save_shadow_army_system};
+
+/// Saves the shadow army with the `storage` crate so it persists across runs.
+#[cfg(all(feature = "storage", not(target_arch = "wasm32")))]
+mod persistence {
+    use bevy::prelude::*;
+    use engine::spawnable::mob::shadow::ShadowArmyResource;
+    use storage::Storage;
+
+    /// Storage key of the shadow army roster.
+    const SHADOW_ARMY_KEY: &str = "shadow_army";
+
+    /// Replaces the default roster with the saved one, if any.
+    pub fn load_shadow_army_system(mut army: ResMut<ShadowArmyResource>) {
+        let Some(storage) = Storage::default_location() else {
+            return;
+        };
+        match storage.load::<ShadowArmyResource>(SHADOW_ARMY_KEY) {
+            Ok(Some(saved)) => *army = saved,
+            Ok(None) => {},
+            Err(error) => error!("Failed to load shadow army: {error}"),
+        }
+    }
+
+    /// Writes the roster to storage.
+    pub fn save_shadow_army_system(army: Res<ShadowArmyResource>) {
+        let Some(storage) = Storage::default_location() else {
+            return;
+        };
+        if let Err(error) = storage.save(SHADOW_ARMY_KEY, &*army) {
+            error!("Failed to save shadow army: {error}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use engine::health::HealthComponent;
+    use engine::spawnable::mob::shadow::ShadowTargetPolicy;
+    use engine::spawnable::mob::{
+        EnemyComponent, EnemyMobType, EnemyTypeComponent,
+    };
+
+    use super::*;
+    use crate::spawnable::mob::enemy::enemy_death_system;
+
+    fn app() -> App {
+        let extractions: ShadowExtractionsResource = ron::de::from_bytes(
+            include_bytes!("../../../assets/data/shadow_extractions.ron"),
+        )
+        .unwrap();
+        let mut app = App::new();
+        app.add_event::<EnemyDefeatedEvent>()
+            .init_resource::<ShadowArmyResource>()
+            .insert_resource(extractions)
+            .add_systems(
+                Update,
+                (
+                    enemy_death_system,
+                    shadow_extraction_system,
+                )
+                    .chain(),
+            );
+        app.world_mut().spawn(ShadowSummonComponent {
+            max_summons: 1,
+            max_radius: 100.0,
+            health_percentage: 1.0,
+            damage_percentage: 1.0,
+            lifetime: None,
+            move_speed: 100.0,
+            attack_range: 10.0,
+            attack_interval: 1.0,
+            target_policy: ShadowTargetPolicy::default(),
+            on_hit_status_effect: None,
+            knockback: 0.0,
+        });
+        app
+    }
+
+    #[test]
+    fn killed_boss_is_extracted_into_the_army() {
+        let mut app = app();
+        let mut health = HealthComponent::new(10);
+        health.take_damage(10);
+        let enemy = app
+            .world_mut()
+            .spawn((
+                EnemyComponent,
+                EnemyTypeComponent {
+                    mob_type: EnemyMobType::Knight,
+                    boss: true,
+                },
+                health,
+            ))
+            .id();
+
+        app.update();
+
+        assert!(app.world().get_entity(enemy).is_err());
+        let army = app.world().resource::<ShadowArmyResource>();
+        assert_eq!(
+            army.get(AllyMobType::Igris).map(|record| &record.name[..]),
+            Some("Igris")
+        );
+    }
+}
@@ -0,0 +1,546 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use engine::combat::{CombatRngResource, DamageDealtEvent, HitStunComponent};
+use engine::health::HealthComponent;
+use engine::spawnable::mob::shadow::{
+    ShadowSoldierComponent, ShadowTargetPolicy,
+};
+use engine::spawnable::mob::EnemyComponent;
+use engine::status_effect::StatusEffectsComponent;
+
+/// Enemy that a shadow may pick as a target.
+struct TargetCandidate {
+    entity: Entity,
+    position: Vec2,
+    health: u32,
+}
+
+impl TargetCandidate {
+    /// Returns `true` if the enemy is within the leash range of the summoner.
+    fn in_leash_range(&self, summoner_position: Vec2, max_radius: f32) -> bool {
+        self.position.distance(summoner_position) <= max_radius
+    }
+}
+
+/// Picks a target among `candidates` according to `policy`.
+///
+/// Only enemies within `max_radius` of the summoner are considered, so shadows
+/// never chase a target out of their leash range. `attacker` is the enemy that
+/// most recently damaged the summoner, if any.
+fn select_target(
+    policy: ShadowTargetPolicy,
+    shadow_position: Vec2,
+    summoner_position: Vec2,
+    max_radius: f32,
+    attacker: Option<Entity>,
+    candidates: &[TargetCandidate],
+) -> Option<Entity> {
+    let mut in_range = candidates.iter().filter(|candidate| {
+        candidate.in_leash_range(summoner_position, max_radius)
+    });
+
+    let target = match policy {
+        ShadowTargetPolicy::Nearest => in_range.min_by(|a, b| {
+            a.position
+                .distance_squared(shadow_position)
+                .total_cmp(&b.position.distance_squared(shadow_position))
+        }),
+        ShadowTargetPolicy::LowestHealth => {
+            in_range.min_by_key(|candidate| candidate.health)
+        },
+        ShadowTargetPolicy::AttackingSummoner => {
+            return attacker
+                .filter(|&attacker| {
+                    in_range.any(|candidate| candidate.entity == attacker)
+                })
+                .or_else(|| {
+                    select_target(
+                        ShadowTargetPolicy::Nearest,
+                        shadow_position,
+                        summoner_position,
+                        max_radius,
+                        None,
+                        candidates,
+                    )
+                });
+        },
+    };
+
+    target.map(|candidate| candidate.entity)
+}
+
+/// Keeps every shadow's target valid and picks a new one when needed.
+///
+/// A target is dropped once it dies, despawns or leaves the leash range around
+/// the summoner. Shadows using [`ShadowTargetPolicy::AttackingSummoner`] switch
+/// to whichever enemy damaged their summoner this frame.
+pub fn shadow_target_selection_system(
+    mut damage_dealt_events: EventReader<DamageDealtEvent>,
+    mut soldier_query: Query<
+        (&Transform, &mut ShadowSoldierComponent),
+        Without<HitStunComponent>,
+    >,
+    summoner_query: Query<&Transform, Without<ShadowSoldierComponent>>,
+    enemy_query: Query<
+        (Entity, &Transform, &HealthComponent),
+        With<EnemyComponent>,
+    >,
+) {
+    let mut attackers = HashMap::<Entity, Entity>::new();
+    for event in damage_dealt_events.read() {
+        let Some(source) = event.source else {
+            continue;
+        };
+        if enemy_query.contains(source) {
+            attackers.insert(event.target, source);
+        }
+    }
+
+    let candidates: Vec<TargetCandidate> = enemy_query
+        .iter()
+        .filter(|(_, _, health)| !health.is_dead())
+        .map(
+            |(entity, transform, health)| TargetCandidate {
+                entity,
+                position: transform.translation.truncate(),
+                health: health.current,
+            },
+        )
+        .collect();
+
+    for (transform, mut soldier) in soldier_query.iter_mut() {
+        let Ok(summoner_transform) = summoner_query.get(soldier.summoned_by)
+        else {
+            continue;
+        };
+        let summoner_position = summoner_transform.translation.truncate();
+
+        if soldier.leashing {
+            soldier.target = None;
+            continue;
+        }
+
+        let attacker = attackers.get(&soldier.summoned_by).copied();
+        let target_still_valid = soldier.target.is_some_and(|target| {
+            candidates.iter().any(|candidate| {
+                candidate.entity == target
+                    && candidate
+                        .in_leash_range(summoner_position, soldier.max_radius)
+            })
+        });
+        let switch_to_attacker = soldier.target_policy
+            == ShadowTargetPolicy::AttackingSummoner
+            && attacker.is_some();
+
+        if target_still_valid && !switch_to_attacker {
+            continue;
+        }
+
+        soldier.target = select_target(
+            soldier.target_policy,
+            transform.translation.truncate(),
+            summoner_position,
+            soldier.max_radius,
+            attacker,
+            &candidates,
+        );
+    }
+}
+
+/// Moves shadows towards their target, or back to their summoner.
+///
+/// - A shadow further than `max_radius` from its summoner starts leashing and
+///   runs back, ignoring enemies until it is within `follow_distance` again.
+/// - A shadow with a target closes in until it is within `attack_range`.
+/// - A shadow without a target follows its summoner at `follow_distance`.
+///
+/// Stunned shadows stand still, and feared shadows run away from the source
+/// of the fear.
+pub fn shadow_movement_system(
+    time: Res<Time>,
+    mut soldier_query: Query<
+        (
+            &mut Transform,
+            &mut ShadowSoldierComponent,
+            Option<&StatusEffectsComponent>,
+        ),
+        Without<HitStunComponent>,
+    >,
+    other_query: Query<&Transform, Without<ShadowSoldierComponent>>,
+) {
+    for (mut transform, mut soldier, status_effects) in soldier_query.iter_mut()
+    {
+        let Ok(summoner_transform) = other_query.get(soldier.summoned_by)
+        else {
+            continue;
+        };
+        if status_effects.is_some_and(StatusEffectsComponent::is_stunned) {
+            continue;
+        }
+        let position = transform.translation.truncate();
+        let summoner_position = summoner_transform.translation.truncate();
+        let distance_to_summoner = position.distance(summoner_position);
+        let speed = soldier.speed
+            * status_effects
+                .map_or(1.0, StatusEffectsComponent::movement_multiplier);
+
+        // Feared shadows run away from the source of the fear
+        let fear_position = status_effects
+            .and_then(StatusEffectsComponent::fear_source)
+            .and_then(|source| other_query.get(source).ok())
+            .map(|source_transform| source_transform.translation.truncate());
+        if let Some(fear_position) = fear_position {
+            let direction = (position - fear_position).normalize_or(Vec2::X);
+            transform.translation +=
+                (direction * speed * time.delta_secs()).extend(0.0);
+            face(&mut transform, direction);
+            continue;
+        }
+
+        if distance_to_summoner > soldier.max_radius {
+            soldier.leashing = true;
+            soldier.target = None;
+        } else if soldier.leashing
+            && distance_to_summoner <= soldier.follow_distance()
+        {
+            soldier.leashing = false;
+        }
+
+        let target_position = soldier
+            .target
+            .and_then(|target| other_query.get(target).ok())
+            .map(|target_transform| target_transform.translation.truncate());
+
+        let (destination, stop_distance) = match target_position {
+            Some(target_position) if !soldier.leashing => {
+                (target_position, soldier.attack_range)
+            },
+            _ if soldier.leashing => (summoner_position, 0.0),
+            _ => (
+                summoner_position,
+                soldier.follow_distance(),
+            ),
+        };
+
+        let offset = destination - position;
+        let distance = offset.length();
+        if distance <= stop_distance {
+            continue;
+        }
+
+        let step = (speed * time.delta_secs()).min(distance - stop_distance);
+        let direction = offset / distance;
+        transform.translation += (direction * step).extend(0.0);
+        face(&mut transform, direction);
+    }
+}
+
+/// Flips a shadow's sprite to face the horizontal side of `direction`.
+fn face(transform: &mut Transform, direction: Vec2) {
+    if direction.x.abs() > f32::EPSILON {
+        transform.scale.x = transform.scale.x.abs() * direction.x.signum();
+    }
+}
+
+/// Makes shadows hit their target whenever it is within `attack_range`.
+/// Every hit rolls for a critical strike with the summoner's stats. Stunned
+/// shadows hold their attacks.
+pub fn shadow_attack_system(
+    time: Res<Time>,
+    mut rng: ResMut<CombatRngResource>,
+    mut damage_dealt_events: EventWriter<DamageDealtEvent>,
+    mut soldier_query: Query<
+        (
+            Entity,
+            &Transform,
+            &mut ShadowSoldierComponent,
+            Option<&StatusEffectsComponent>,
+        ),
+        Without<HitStunComponent>,
+    >,
+    target_query: Query<&Transform, Without<ShadowSoldierComponent>>,
+) {
+    for (entity, transform, mut soldier, status_effects) in
+        soldier_query.iter_mut()
+    {
+        soldier.attack_timer.tick(time.delta());
+        if status_effects.is_some_and(StatusEffectsComponent::is_stunned) {
+            continue;
+        }
+
+        let Some(target) = soldier.target else {
+            continue;
+        };
+        let Ok(target_transform) = target_query.get(target) else {
+            continue;
+        };
+
+        let distance = transform
+            .translation
+            .truncate()
+            .distance(target_transform.translation.truncate());
+        if distance > soldier.attack_range || !soldier.attack_timer.finished() {
+            continue;
+        }
+
+        soldier.attack_timer.reset();
+        let roll = soldier.critical.roll(soldier.damage, &mut rng.0);
+        damage_dealt_events.send(DamageDealtEvent {
+            target,
+            damage: roll.damage,
+            damage_type: soldier.damage_type,
+            is_critical: roll.is_critical,
+            source: Some(entity),
+            status_effect: soldier.on_hit_status_effect.clone(),
+            knockback: soldier.knockback,
+        });
+    }
+}
+
+/// Despawns shadows whose lifetime ran out, who died, or whose summoner is gone.
+pub fn shadow_lifetime_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut soldier_query: Query<(
+        Entity,
+        &mut ShadowSoldierComponent,
+        &HealthComponent,
+    )>,
+    summoner_query: Query<(), Without<ShadowSoldierComponent>>,
+) {
+    for (entity, mut soldier, health) in soldier_query.iter_mut() {
+        let expired = soldier
+            .lifetime
+            .as_mut()
+            .is_some_and(|lifetime| lifetime.tick(time.delta()).finished());
+
+        if expired
+            || health.is_dead()
+            || !summoner_query.contains(soldier.summoned_by)
+        {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use engine::combat::{CriticalStats, DamageType};
+    use engine::spawnable::mob::AllyMobType;
+
+    use super::*;
+
+    const MAX_RADIUS: f32 = 100.0;
+    const ATTACK_INTERVAL: f32 = 1.0;
+
+    fn candidate(entity: u32, x: f32, health: u32) -> TargetCandidate {
+        TargetCandidate {
+            entity: Entity::from_raw(entity),
+            position: Vec2::new(x, 0.0),
+            health,
+        }
+    }
+
+    /// Enemies 1 and 2 are within the leash range of a summoner at the
+    /// origin. Enemy 3 is closest to the shadow and weakest, but out of
+    /// range.
+    fn candidates() -> [TargetCandidate; 3] {
+        [
+            candidate(1, 60.0, 50),
+            candidate(2, -90.0, 10),
+            candidate(3, 150.0, 1),
+        ]
+    }
+
+    fn select(
+        policy: ShadowTargetPolicy,
+        attacker: Option<u32>,
+    ) -> Option<Entity> {
+        select_target(
+            policy,
+            Vec2::new(80.0, 0.0),
+            Vec2::ZERO,
+            MAX_RADIUS,
+            attacker.map(Entity::from_raw),
+            &candidates(),
+        )
+    }
+
+    #[test]
+    fn nearest_picks_the_closest_enemy_in_leash_range() {
+        assert_eq!(
+            select(ShadowTargetPolicy::Nearest, None),
+            Some(Entity::from_raw(1))
+        );
+    }
+
+    #[test]
+    fn lowest_health_picks_the_weakest_enemy_in_leash_range() {
+        assert_eq!(
+            select(ShadowTargetPolicy::LowestHealth, None),
+            Some(Entity::from_raw(2))
+        );
+    }
+
+    #[test]
+    fn attacking_summoner_picks_the_attacker_in_leash_range() {
+        let policy = ShadowTargetPolicy::AttackingSummoner;
+        assert_eq!(
+            select(policy, Some(2)),
+            Some(Entity::from_raw(2))
+        );
+        // Out of range attackers and no attacker fall back to the nearest
+        assert_eq!(
+            select(policy, Some(3)),
+            Some(Entity::from_raw(1))
+        );
+        assert_eq!(
+            select(policy, None),
+            Some(Entity::from_raw(1))
+        );
+    }
+
+    #[test]
+    fn no_target_without_enemies_in_leash_range() {
+        let out_of_range = [candidate(3, 150.0, 1)];
+        assert_eq!(
+            select_target(
+                ShadowTargetPolicy::Nearest,
+                Vec2::ZERO,
+                Vec2::ZERO,
+                MAX_RADIUS,
+                None,
+                &out_of_range,
+            ),
+            None
+        );
+    }
+
+    fn soldier(summoned_by: Entity) -> ShadowSoldierComponent {
+        ShadowSoldierComponent {
+            mob_type: AllyMobType::Igris,
+            summoned_by,
+            max_radius: MAX_RADIUS,
+            speed: 100.0,
+            attack_range: 10.0,
+            damage: 5,
+            damage_type: DamageType::Physical,
+            critical: CriticalStats::default(),
+            attack_timer: Timer::from_seconds(ATTACK_INTERVAL, TimerMode::Once),
+            on_hit_status_effect: None,
+            knockback: 0.0,
+            target_policy: ShadowTargetPolicy::Nearest,
+            target: None,
+            leashing: false,
+            lifetime: None,
+        }
+    }
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.init_resource::<Time>()
+            .init_resource::<CombatRngResource>()
+            .add_event::<DamageDealtEvent>()
+            .add_systems(
+                Update,
+                (
+                    shadow_movement_system,
+                    shadow_attack_system,
+                )
+                    .chain(),
+            );
+        app
+    }
+
+    /// Advances time and returns how many hits the shadows dealt.
+    fn advance(app: &mut App, seconds: f32) -> usize {
+        app.world_mut()
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_secs_f32(seconds));
+        app.update();
+        app.world_mut()
+            .resource_mut::<Events<DamageDealtEvent>>()
+            .drain()
+            .count()
+    }
+
+    #[test]
+    fn shadow_past_max_radius_leashes_back_to_its_summoner() {
+        let mut app = app();
+        let summoner = app.world_mut().spawn(Transform::default()).id();
+        let enemy = app
+            .world_mut()
+            .spawn(Transform::from_xyz(130.0, 0.0, 0.0))
+            .id();
+        let mut shadow_soldier = soldier(summoner);
+        shadow_soldier.target = Some(enemy);
+        let shadow = app
+            .world_mut()
+            .spawn((
+                shadow_soldier,
+                Transform::from_xyz(120.0, 0.0, 0.0),
+            ))
+            .id();
+
+        advance(&mut app, 0.1);
+        let soldier =
+            app.world().get::<ShadowSoldierComponent>(shadow).unwrap();
+        assert!(soldier.leashing);
+        assert_eq!(soldier.target, None);
+        let x = app.world().get::<Transform>(shadow).unwrap().translation.x;
+        assert!(x < 120.0);
+
+        // Leashing ends once the shadow is back within follow distance
+        app.world_mut()
+            .entity_mut(shadow)
+            .insert(Transform::from_xyz(40.0, 0.0, 0.0));
+        advance(&mut app, 0.1);
+        let soldier =
+            app.world().get::<ShadowSoldierComponent>(shadow).unwrap();
+        assert!(!soldier.leashing);
+    }
+
+    #[test]
+    fn hits_every_interval_and_as_soon_as_the_target_is_in_range() {
+        let mut app = app();
+        let summoner = app.world_mut().spawn(Transform::default()).id();
+        let target = app
+            .world_mut()
+            .spawn(Transform::from_xyz(60.0, 0.0, 0.0))
+            .id();
+        let mut shadow_soldier = soldier(summoner);
+        shadow_soldier.target = Some(target);
+        shadow_soldier.speed = 0.0;
+        let shadow = app
+            .world_mut()
+            .spawn((shadow_soldier, Transform::default()))
+            .id();
+
+        assert_eq!(
+            advance(&mut app, ATTACK_INTERVAL * 1.3),
+            0
+        );
+        app.world_mut()
+            .entity_mut(target)
+            .insert(Transform::from_xyz(5.0, 0.0, 0.0));
+        assert_eq!(
+            advance(&mut app, ATTACK_INTERVAL * 0.1),
+            1
+        );
+        assert_eq!(
+            advance(&mut app, ATTACK_INTERVAL * 0.5),
+            0
+        );
+        assert_eq!(
+            advance(&mut app, ATTACK_INTERVAL * 0.5),
+            1
+        );
+        assert!(app
+            .world()
+            .get::<ShadowSoldierComponent>(shadow)
+            .is_some_and(|soldier| soldier.target == Some(target)));
+    }
+}
@@ -0,0 +1,175 @@
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+use engine::combat::CombatRngResource;
+use engine::player::PlayerComponent;
+use engine::spawnable::mob::{
+    EnemyComponent, EnemyMobType, MobSpawnsResource, MobType, SpawnMobEvent,
+};
+use rand::distributions::WeightedIndex;
+use rand::prelude::*;
+
+use crate::game::resources::GameResource;
+
+/// Progress of the enemy spawner through the current run.
+#[derive(Resource)]
+pub struct EnemySpawnerResource {
+    spawn_timer: Timer,
+    /// Seconds since the run started.
+    run_time: f32,
+}
+
+impl EnemySpawnerResource {
+    pub fn new(mob_spawns: &MobSpawnsResource) -> Self {
+        Self {
+            spawn_timer: Timer::from_seconds(
+                mob_spawns.spawn_interval,
+                TimerMode::Repeating,
+            ),
+            run_time: 0.0,
+        }
+    }
+}
+
+/// Restarts the spawner at the start of every run.
+pub fn init_enemy_spawner_system(
+    mut commands: Commands,
+    mob_spawns: Res<MobSpawnsResource>,
+) {
+    commands.insert_resource(EnemySpawnerResource::new(&mob_spawns));
+}
+
+/// Spawns enemies around the players following `mobs.ron`.
+///
+/// Every `spawn_interval` an enemy type is picked by weight and spawned
+/// `spawn_distance` away from a random player, unless `max_enemies` are
+/// already alive. Bosses spawn once each when the run reaches their time,
+/// regardless of the enemy cap. Spawn positions are kept inside the arena.
+#[allow(clippy::too_many_arguments)]
+pub fn enemy_spawner_system(
+    time: Res<Time>,
+    mob_spawns: Res<MobSpawnsResource>,
+    game_parameters: Res<GameResource>,
+    mut spawner: ResMut<EnemySpawnerResource>,
+    mut rng: ResMut<CombatRngResource>,
+    mut spawn_mob_events: EventWriter<SpawnMobEvent>,
+    player_query: Query<&Transform, With<PlayerComponent>>,
+    enemy_query: Query<(), With<EnemyComponent>>,
+) {
+    let previous_time = spawner.run_time;
+    spawner.run_time += time.delta_secs();
+    spawner.spawn_timer.tick(time.delta());
+
+    let Some(center) = player_query
+        .iter()
+        .choose(&mut rng.0)
+        .map(|transform| transform.translation.truncate())
+    else {
+        return;
+    };
+    let mut spawns: Vec<(EnemyMobType, bool)> = mob_spawns
+        .bosses
+        .iter()
+        .filter(|boss| {
+            previous_time < boss.time && boss.time <= spawner.run_time
+        })
+        .map(|boss| (boss.mob_type, true))
+        .collect();
+
+    if spawner.spawn_timer.just_finished()
+        && enemy_query.iter().count() < mob_spawns.max_enemies
+    {
+        if let Ok(weights) = WeightedIndex::new(
+            mob_spawns.enemies.iter().map(|spawn| spawn.weight),
+        ) {
+            let index = weights.sample(&mut rng.0);
+            spawns.push((
+                mob_spawns.enemies[index].mob_type,
+                false,
+            ));
+        }
+    }
+
+    let max = game_parameters.arena_size * 0.5;
+    for (mob_type, boss) in spawns {
+        let offset = Vec2::from_angle(rng.0.gen_range(0.0..TAU))
+            * mob_spawns.spawn_distance;
+        spawn_mob_events.send(SpawnMobEvent {
+            mob_type: MobType::Enemy(mob_type),
+            position: (center + offset).clamp(-max, max),
+            rotation: Quat::IDENTITY,
+            boss,
+            summoned_by: None,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use engine::spawnable::mob::{BossSpawnData, EnemySpawnData};
+
+    use super::*;
+    use crate::game::data::GameData;
+
+    fn app(mob_spawns: MobSpawnsResource) -> App {
+        let data = GameData::load(None).unwrap();
+        let mut app = App::new();
+        app.init_resource::<Time>()
+            .add_event::<SpawnMobEvent>()
+            .insert_resource(CombatRngResource::seeded(0))
+            .insert_resource(data.game_parameters)
+            .insert_resource(EnemySpawnerResource::new(&mob_spawns))
+            .insert_resource(mob_spawns)
+            .add_systems(Update, enemy_spawner_system);
+        app.world_mut()
+            .spawn((PlayerComponent, Transform::default()));
+        app
+    }
+
+    fn advance(app: &mut App, seconds: f32) -> Vec<(MobType, bool)> {
+        app.world_mut()
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_secs_f32(seconds));
+        app.update();
+        app.world_mut()
+            .resource_mut::<Events<SpawnMobEvent>>()
+            .drain()
+            .map(|event| (event.mob_type, event.boss))
+            .collect()
+    }
+
+    #[test]
+    fn spawns_on_interval_up_to_max_enemies_and_bosses_on_time() {
+        let mut app = app(MobSpawnsResource {
+            spawn_interval: 1.0,
+            spawn_distance: 100.0,
+            max_enemies: 1,
+            enemies: vec![EnemySpawnData {
+                mob_type: EnemyMobType::Goblin,
+                weight: 1,
+            }],
+            bosses: vec![BossSpawnData {
+                mob_type: EnemyMobType::Knight,
+                time: 2.5,
+            }],
+        });
+        let goblin = (
+            MobType::Enemy(EnemyMobType::Goblin),
+            false,
+        );
+        let knight = (
+            MobType::Enemy(EnemyMobType::Knight),
+            true,
+        );
+
+        assert!(advance(&mut app, 0.5).is_empty());
+        assert_eq!(advance(&mut app, 0.5), vec![goblin]);
+
+        app.world_mut().spawn(EnemyComponent);
+        assert!(advance(&mut app, 1.0).is_empty());
+        assert_eq!(advance(&mut app, 0.5), vec![knight]);
+        assert!(advance(&mut app, 1.0).is_empty());
+    }
+}
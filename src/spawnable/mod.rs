@@ -1,3 +1,4 @@
 // TODO: spawn olacak nesnelerin islemleri
 
+pub mod mob;
 pub mod projectile;
@@ -1,12 +1,14 @@
 pub mod systems;
 
-use assets::spawnable::projectile::ProjectileAssets;
 use bevy::prelude::*;
-use engine::spawnable::projectile::ProjectileComponent;
 use engine::states::app::AppStates;
 
-use crate::game::resources::GameResource;
-use crate::player::character::CharactersResource;
+use crate::spawnable::projectile::systems::behavior::{
+    projectile_bounce_system, projectile_homing_system, projectile_orbit_system,
+};
+use crate::spawnable::projectile::systems::collision::projectile_hit_system;
+use crate::spawnable::projectile::systems::lifetime::projectile_lifetime_system;
+use crate::spawnable::projectile::systems::spawn::fire_projectiles_system;
 use crate::spawnable::projectile::systems::transform::update_projectile_transform;
 
 pub struct ProjectilePlugin;
@@ -15,7 +17,17 @@ impl Plugin for ProjectilePlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(
             Update,
-            (update_projectile_transform).run_if(in_state(AppStates::InGame)),
+            (
+                fire_projectiles_system,
+                projectile_homing_system,
+                update_projectile_transform,
+                projectile_orbit_system,
+                projectile_bounce_system,
+                projectile_hit_system,
+                projectile_lifetime_system,
+            )
+                .chain()
+                .run_if(in_state(AppStates::InGame)),
         );
     }
 }
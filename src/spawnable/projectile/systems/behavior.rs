@@ -0,0 +1,202 @@
+use bevy::prelude::*;
+use engine::health::HealthComponent;
+use engine::spawnable::mob::EnemyComponent;
+use engine::spawnable::projectile::behavior::{
+    bounce, steer, BounceComponent, HomingComponent, OrbitComponent,
+};
+use engine::spawnable::projectile::ProjectileComponent;
+use engine::spawnable::Faction;
+
+use crate::game::resources::GameResource;
+
+/// Turns homing projectiles toward the nearest living hostile target within
+/// range that they did not hit yet, by at most `turn_rate` radians per second.
+pub fn projectile_homing_system(
+    time: Res<Time>,
+    mut projectile_query: Query<(
+        &Transform,
+        &mut ProjectileComponent,
+        &HomingComponent,
+    )>,
+    target_query: Query<(
+        Entity,
+        &Transform,
+        &HealthComponent,
+        Has<EnemyComponent>,
+    )>,
+) {
+    for (transform, mut projectile, homing) in projectile_query.iter_mut() {
+        let faction = projectile.projectile_type.get_faction();
+        let position = transform.translation.truncate();
+        let target = target_query
+            .iter()
+            .filter(|(target, _, health, is_enemy)| {
+                !health.is_dead()
+                    && faction.is_hostile_to(Faction::of(*is_enemy))
+                    && !projectile.hit.contains(target)
+            })
+            .map(|(_, target_transform, _, _)| {
+                target_transform.translation.truncate()
+            })
+            .filter(|target| target.distance(position) <= homing.range)
+            .min_by(|a, b| {
+                a.distance_squared(position)
+                    .total_cmp(&b.distance_squared(position))
+            });
+
+        if let Some(target) = target {
+            projectile.velocity = steer(
+                projectile.velocity,
+                target - position,
+                homing.turn_rate * time.delta_secs(),
+            );
+        }
+    }
+}
+
+/// Moves orbiting projectiles around their center and turns them along their
+/// path. Projectiles whose center is gone are despawned.
+pub fn projectile_orbit_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut projectile_query: Query<
+        (
+            Entity,
+            &mut Transform,
+            &mut OrbitComponent,
+        ),
+        With<ProjectileComponent>,
+    >,
+    center_query: Query<&Transform, Without<ProjectileComponent>>,
+) {
+    for (entity, mut transform, mut orbit) in projectile_query.iter_mut() {
+        let Ok(center_transform) = center_query.get(orbit.center) else {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        };
+
+        let offset = orbit.advance(time.delta_secs());
+        let center = center_transform.translation.truncate();
+        transform.translation =
+            (center + offset).extend(transform.translation.z);
+        transform.rotation = Quat::from_rotation_z(
+            offset.to_angle()
+                + std::f32::consts::FRAC_PI_2 * orbit.angular_speed.signum(),
+        );
+    }
+}
+
+/// Bounces projectiles off the arena bounds while they have bounces left.
+pub fn projectile_bounce_system(
+    game_parameters: Res<GameResource>,
+    mut projectile_query: Query<(
+        &Transform,
+        &mut ProjectileComponent,
+        &mut BounceComponent,
+    )>,
+) {
+    let max = game_parameters.arena_size * 0.5;
+    for (transform, mut projectile, mut bounces) in projectile_query.iter_mut()
+    {
+        if bounces.remaining == 0 {
+            continue;
+        }
+        if let Some(velocity) = bounce(
+            transform.translation.truncate(),
+            projectile.velocity,
+            -max,
+            max,
+        ) {
+            projectile.velocity = velocity;
+            bounces.remaining -= 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::super::test_util::spawn_projectile;
+    use super::*;
+    use crate::game::data::GameData;
+
+    fn velocity(app: &App, projectile: Entity) -> Vec2 {
+        app.world()
+            .get::<ProjectileComponent>(projectile)
+            .unwrap()
+            .velocity
+    }
+
+    #[test]
+    fn homing_turns_toward_nearest_enemy_at_turn_rate() {
+        let mut app = App::new();
+        app.init_resource::<Time>()
+            .add_systems(Update, projectile_homing_system);
+        app.world_mut().spawn((
+            EnemyComponent,
+            HealthComponent::new(10),
+            Transform::from_xyz(0.0, 100.0, 0.0),
+        ));
+        app.world_mut().spawn((
+            EnemyComponent,
+            HealthComponent::new(10),
+            Transform::from_xyz(0.0, -300.0, 0.0),
+        ));
+        let projectile = spawn_projectile(
+            &mut app,
+            Faction::Ally,
+            Vec2::ZERO,
+            Vec2::X * 100.0,
+        );
+        app.world_mut()
+            .entity_mut(projectile)
+            .insert(HomingComponent {
+                turn_rate: 1.0,
+                range: 200.0,
+            });
+
+        app.world_mut()
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_millis(500));
+        app.update();
+
+        let velocity = velocity(&app, projectile);
+        assert!((velocity.to_angle() - 0.5).abs() < 1e-4);
+        assert!((velocity.length() - 100.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn bounces_off_arena_bounds_until_none_left() {
+        let mut app = App::new();
+        let mut game_parameters = GameData::load(None).unwrap().game_parameters;
+        game_parameters.arena_size = Vec2::splat(200.0);
+        app.insert_resource(game_parameters)
+            .add_systems(Update, projectile_bounce_system);
+        let projectile = spawn_projectile(
+            &mut app,
+            Faction::Ally,
+            Vec2::new(100.0, 0.0),
+            Vec2::new(10.0, 5.0),
+        );
+        app.world_mut()
+            .entity_mut(projectile)
+            .insert(BounceComponent { remaining: 1 });
+
+        app.update();
+        assert_eq!(
+            velocity(&app, projectile),
+            Vec2::new(-10.0, 5.0)
+        );
+
+        app.world_mut()
+            .get_mut::<ProjectileComponent>(projectile)
+            .unwrap()
+            .velocity = Vec2::new(10.0, 5.0);
+        app.update();
+        assert_eq!(
+            velocity(&app, projectile),
+            Vec2::new(10.0, 5.0)
+        );
+    }
+}
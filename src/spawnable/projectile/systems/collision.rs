@@ -0,0 +1,252 @@
+use bevy::prelude::*;
+use engine::combat::{CombatRngResource, DamageDealtEvent};
+use engine::health::HealthComponent;
+use engine::spawnable::mob::EnemyComponent;
+use engine::spawnable::projectile::behavior::{
+    ChainComponent, PierceComponent,
+};
+use engine::spawnable::projectile::ProjectileComponent;
+use engine::spawnable::Faction;
+
+/// Hits the first living hostile target within a projectile's radius that it
+/// did not hit yet.
+///
+/// Ally projectiles hit enemies, while enemy projectiles hit players and
+/// their summons but never other enemies (see [`Faction::is_hostile_to`]).
+///
+/// After a hit the projectile:
+/// - keeps flying if it can still pierce ([`PierceComponent`]),
+/// - otherwise jumps toward the nearest hostile target within range of the
+///   target if it can still chain ([`ChainComponent`]), with its damage
+///   reduced by the chain's falloff,
+/// - otherwise despawns.
+pub fn projectile_hit_system(
+    mut commands: Commands,
+    mut rng: ResMut<CombatRngResource>,
+    mut damage_dealt_events: EventWriter<DamageDealtEvent>,
+    mut projectile_query: Query<(
+        Entity,
+        &Transform,
+        &mut ProjectileComponent,
+        Option<&mut PierceComponent>,
+        Option<&mut ChainComponent>,
+    )>,
+    target_query: Query<(
+        Entity,
+        &Transform,
+        &HealthComponent,
+        Has<EnemyComponent>,
+    )>,
+) {
+    for (entity, transform, mut projectile, pierce, chain) in
+        projectile_query.iter_mut()
+    {
+        let faction = projectile.projectile_type.get_faction();
+        let position = transform.translation.truncate();
+        let Some((target, target_transform, _, _)) =
+            target_query
+                .iter()
+                .find(|(target, target_transform, health, is_enemy)| {
+                    !health.is_dead()
+                        && faction.is_hostile_to(Faction::of(*is_enemy))
+                        && !projectile.hit.contains(target)
+                        && target_transform
+                            .translation
+                            .truncate()
+                            .distance(position)
+                            <= projectile.radius
+                })
+        else {
+            continue;
+        };
+
+        let roll = projectile.critical.roll(projectile.damage, &mut rng.0);
+        damage_dealt_events.send(DamageDealtEvent {
+            target,
+            damage: roll.damage,
+            damage_type: projectile.damage_type,
+            is_critical: roll.is_critical,
+            source: Some(projectile.owner),
+            status_effect: None,
+            knockback: 0.0,
+        });
+        projectile.hit.insert(target);
+
+        if let Some(mut pierce) = pierce.filter(|pierce| pierce.remaining > 0) {
+            pierce.remaining -= 1;
+            continue;
+        }
+
+        if let Some(mut chain) = chain.filter(|chain| chain.remaining > 0) {
+            let target_position = target_transform.translation.truncate();
+            let next_target = target_query
+                .iter()
+                .filter(|(next, next_transform, health, is_enemy)| {
+                    !health.is_dead()
+                        && faction.is_hostile_to(Faction::of(*is_enemy))
+                        && !projectile.hit.contains(next)
+                        && next_transform
+                            .translation
+                            .truncate()
+                            .distance(target_position)
+                            <= chain.range
+                })
+                .map(|(_, next_transform, _, _)| {
+                    next_transform.translation.truncate()
+                })
+                .min_by(|a, b| {
+                    a.distance_squared(target_position)
+                        .total_cmp(&b.distance_squared(target_position))
+                });
+
+            if let Some(next_position) = next_target {
+                let speed = projectile.velocity.length();
+                projectile.velocity =
+                    (next_position - position).normalize_or_zero() * speed;
+                projectile.damage =
+                    (projectile.damage as f32 * chain.falloff).round() as u32;
+                chain.remaining -= 1;
+                continue;
+            }
+        }
+
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use engine::player::PlayerComponent;
+
+    use super::super::test_util::spawn_projectile;
+    use super::*;
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_event::<DamageDealtEvent>()
+            .insert_resource(CombatRngResource::seeded(0))
+            .add_systems(Update, projectile_hit_system);
+        app
+    }
+
+    fn spawn_enemy(app: &mut App, position: Vec2) -> Entity {
+        app.world_mut()
+            .spawn((
+                EnemyComponent,
+                HealthComponent::new(100),
+                Transform::from_translation(position.extend(0.0)),
+            ))
+            .id()
+    }
+
+    fn damage_dealt(app: &App) -> Vec<(Entity, u32)> {
+        let events = app.world().resource::<Events<DamageDealtEvent>>();
+        events
+            .get_cursor()
+            .read(events)
+            .map(|event| (event.target, event.damage))
+            .collect()
+    }
+
+    #[test]
+    fn plain_projectile_despawns_on_hit() {
+        let mut app = app();
+        let enemy = spawn_enemy(&mut app, Vec2::ZERO);
+        let projectile =
+            spawn_projectile(&mut app, Faction::Ally, Vec2::ZERO, Vec2::X);
+
+        app.update();
+
+        assert_eq!(damage_dealt(&app), vec![(enemy, 20)]);
+        assert!(app.world().get_entity(projectile).is_err());
+    }
+
+    #[test]
+    fn piercing_projectile_hits_each_target_once() {
+        let mut app = app();
+        let enemy = spawn_enemy(&mut app, Vec2::ZERO);
+        let projectile =
+            spawn_projectile(&mut app, Faction::Ally, Vec2::ZERO, Vec2::X);
+        app.world_mut()
+            .entity_mut(projectile)
+            .insert(PierceComponent { remaining: 1 });
+
+        app.update();
+        app.update();
+
+        assert_eq!(damage_dealt(&app), vec![(enemy, 20)]);
+        let pierce = app.world().get::<PierceComponent>(projectile).unwrap();
+        assert_eq!(pierce.remaining, 0);
+    }
+
+    #[test]
+    fn chaining_projectile_jumps_to_nearest_enemy_with_falloff() {
+        let mut app = app();
+        let first = spawn_enemy(&mut app, Vec2::ZERO);
+        let near = spawn_enemy(&mut app, Vec2::new(0.0, 100.0));
+        spawn_enemy(&mut app, Vec2::new(150.0, 0.0));
+        let projectile = spawn_projectile(
+            &mut app,
+            Faction::Ally,
+            Vec2::ZERO,
+            Vec2::X * 10.0,
+        );
+        app.world_mut()
+            .entity_mut(projectile)
+            .insert(ChainComponent {
+                remaining: 1,
+                range: 120.0,
+                falloff: 0.5,
+            });
+
+        app.update();
+
+        assert_eq!(damage_dealt(&app), vec![(first, 20)]);
+        let projectile_component =
+            app.world().get::<ProjectileComponent>(projectile).unwrap();
+        assert_eq!(projectile_component.damage, 10);
+        assert!(
+            (projectile_component.velocity - Vec2::Y * 10.0).length() < 1e-4
+        );
+
+        app.world_mut()
+            .entity_mut(projectile)
+            .insert(Transform::from_xyz(0.0, 100.0, 0.0));
+        app.update();
+
+        assert!(damage_dealt(&app).contains(&(near, 10)));
+        assert!(app.world().get_entity(projectile).is_err());
+    }
+
+    #[test]
+    fn enemy_projectile_hits_players_but_not_enemies() {
+        let mut app = app();
+        spawn_enemy(&mut app, Vec2::ZERO);
+        let player = app
+            .world_mut()
+            .spawn((
+                PlayerComponent,
+                HealthComponent::new(100),
+                Transform::from_xyz(0.0, 100.0, 0.0),
+            ))
+            .id();
+        let projectile = spawn_projectile(
+            &mut app,
+            Faction::Enemy,
+            Vec2::ZERO,
+            Vec2::Y,
+        );
+
+        app.update();
+
+        assert!(damage_dealt(&app).is_empty());
+
+        app.world_mut()
+            .entity_mut(projectile)
+            .insert(Transform::from_xyz(0.0, 100.0, 0.0));
+        app.update();
+
+        assert_eq!(damage_dealt(&app), vec![(player, 20)]);
+        assert!(app.world().get_entity(projectile).is_err());
+    }
+}
@@ -0,0 +1,15 @@
+use bevy::prelude::*;
+use engine::spawnable::projectile::ProjectileComponent;
+
+/// Despawns projectiles whose lifetime ran out.
+pub fn projectile_lifetime_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut projectile_query: Query<(Entity, &mut ProjectileComponent)>,
+) {
+    for (entity, mut projectile) in projectile_query.iter_mut() {
+        if projectile.lifetime.tick(time.delta()).finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
@@ -1 +1,7 @@
-pub mod transform;
\ No newline at end of file
+pub mod behavior;
+pub mod collision;
+pub mod lifetime;
+pub mod spawn;
+#[cfg(test)]
+mod test_util;
+pub mod transform;
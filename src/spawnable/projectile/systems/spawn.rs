@@ -0,0 +1,187 @@
+use assets::spawnable::projectile::ProjectileAssets;
+use bevy::prelude::*;
+use engine::aim::AimComponent;
+use engine::combat::DamageType;
+use engine::events::action::FireEvent;
+use engine::player::{PlayerComponent, PlayerOutgoingDamageComponent};
+use engine::spawnable::projectile::behavior::{
+    ProjectileData, ProjectilesResource,
+};
+use engine::spawnable::projectile::{
+    spread_directions, ProjectileComponent, ProjectileType,
+    PROJECTILE_HIT_RADIUS,
+};
+use engine::spawnable::{Faction, SpawnPosition};
+use engine::states::util::GameCleanup;
+use engine::weapon::WeaponComponent;
+
+use crate::game::resources::GameResource;
+
+/// Fires a shot of projectiles for every [`FireEvent`].
+///
+/// The shot is aimed along the player's [`AimComponent`]. It holds
+/// `projectile_count` projectiles spread with [`spread_directions`]. Shots
+/// are trimmed so that at most `max_player_projectiles` player projectiles
+/// are alive at once. The behaviors of the projectile kind are added to every
+/// projectile.
+///
+/// A player holding a [`WeaponComponent`] fires that weapon's projectiles,
+/// with its damage and damage type, at most at its fire rate. A player
+/// without a weapon fires the projectile kind of its character.
+#[allow(clippy::too_many_arguments)]
+pub fn fire_projectiles_system(
+    mut commands: Commands,
+    mut fire_events: EventReader<FireEvent>,
+    game_parameters: Res<GameResource>,
+    projectile_assets: Res<ProjectileAssets>,
+    projectiles_res: Res<ProjectilesResource>,
+    mut weapon_query: Query<&mut WeaponComponent>,
+    projectile_query: Query<&ProjectileComponent>,
+    player_query: Query<
+        (
+            &Transform,
+            &AimComponent,
+            &PlayerOutgoingDamageComponent,
+        ),
+        With<PlayerComponent>,
+    >,
+) {
+    let mut budget =
+        player_projectile_budget(&game_parameters, &projectile_query);
+
+    for event in fire_events.read() {
+        let Ok((transform, aim, outgoing_damage)) = player_query.get(event.0)
+        else {
+            continue;
+        };
+
+        let origin = match outgoing_damage.projectile_spawn_position {
+            SpawnPosition::Global(position) => position,
+            SpawnPosition::Local(offset) => {
+                // Mirror the offset to the side the player aims at
+                let facing = if aim.direction.x < 0.0 { -1.0 } else { 1.0 };
+                transform.translation.truncate()
+                    + Vec2::new(offset.x * facing, offset.y)
+            },
+        };
+        let direction = aim.direction;
+
+        if budget == 0 {
+            continue;
+        }
+        let weapon = weapon_query
+            .iter_mut()
+            .find(|weapon| weapon.owner == event.0);
+        let (projectile, damage, damage_type, speed, count) = match weapon {
+            Some(mut weapon) => {
+                if !weapon.cooldown.finished() {
+                    continue;
+                }
+                weapon.cooldown.reset();
+                (
+                    weapon.data.projectile.clone(),
+                    weapon.data.hit_damage(outgoing_damage.weapon_damage),
+                    weapon.data.damage_type,
+                    outgoing_damage.projectile_speed
+                        * weapon.data.speed_multiplier,
+                    weapon.data.projectile_count
+                        + outgoing_damage.projectile_count.saturating_sub(1),
+                )
+            },
+            None => (
+                outgoing_damage.projectile.clone(),
+                outgoing_damage.weapon_damage,
+                DamageType::default(),
+                outgoing_damage.projectile_speed,
+                outgoing_damage.projectile_count,
+            ),
+        };
+
+        let count = count.min(budget);
+        budget -= count;
+        let projectile_type = ProjectileType::Bullet(Faction::Ally);
+        let Some(projectile_data) = projectiles_res.get(&projectile) else {
+            error!("Projectile kind `{}` not found", projectile);
+            continue;
+        };
+
+        for direction in spread_directions(
+            direction,
+            count,
+            game_parameters.projectile_gap,
+            game_parameters.max_spread_arc,
+        ) {
+            spawn_projectile(
+                &mut commands,
+                &projectile_assets,
+                ProjectileComponent {
+                    projectile_type,
+                    owner: event.0,
+                    damage,
+                    damage_type,
+                    critical: outgoing_damage.critical,
+                    velocity: direction * speed,
+                    radius: PROJECTILE_HIT_RADIUS
+                        * outgoing_damage.projectile_size,
+                    lifetime: Timer::from_seconds(
+                        outgoing_damage.projectile_despawn_time,
+                        TimerMode::Once,
+                    ),
+                    hit: Default::default(),
+                },
+                projectile_data,
+                origin.extend(transform.translation.z + 0.1),
+                outgoing_damage.projectile_size,
+            );
+        }
+    }
+}
+
+/// Number of player projectiles that can still be fired before
+/// `max_player_projectiles` of them are alive at once.
+pub fn player_projectile_budget(
+    game_parameters: &GameResource,
+    projectile_query: &Query<&ProjectileComponent>,
+) -> u32 {
+    let alive = projectile_query
+        .iter()
+        .filter(|projectile| {
+            projectile.projectile_type.get_faction() == Faction::Ally
+        })
+        .count();
+    (game_parameters.max_player_projectiles as u32)
+        .saturating_sub(alive as u32)
+}
+
+/// Spawns a projectile at `translation`, drawn with the sprite of its type
+/// and facing its velocity, and adds the behaviors of its kind.
+/// Returns the spawned entity.
+pub fn spawn_projectile(
+    commands: &mut Commands,
+    projectile_assets: &ProjectileAssets,
+    projectile: ProjectileComponent,
+    projectile_data: &ProjectileData,
+    translation: Vec3,
+    size: f32,
+) -> Entity {
+    let projectile_type = projectile.projectile_type;
+    let rotation = Quat::from_rotation_z(projectile.velocity.to_angle());
+    let mut entity_commands = commands.spawn((
+        Name::new("Projectile"),
+        projectile,
+        Sprite::from_atlas_image(
+            projectile_assets.get_image(&projectile_type),
+            TextureAtlas::from(
+                projectile_assets.get_texture_atlas_layout(&projectile_type),
+            ),
+        ),
+        Transform {
+            translation,
+            rotation,
+            scale: Vec3::splat(size),
+        },
+        GameCleanup,
+    ));
+    projectile_data.insert_behaviors(&mut entity_commands);
+    entity_commands.id()
+}
@@ -0,0 +1,33 @@
+//! Fixtures shared by the projectile system tests.
+
+use bevy::prelude::*;
+use engine::combat::{CriticalStats, DamageType};
+use engine::spawnable::projectile::{ProjectileComponent, ProjectileType};
+use engine::spawnable::Faction;
+
+/// Spawns a 20 damage bullet of `faction` with a radius of 16 that outlives
+/// any test.
+pub fn spawn_projectile(
+    app: &mut App,
+    faction: Faction,
+    position: Vec2,
+    velocity: Vec2,
+) -> Entity {
+    let owner = app.world_mut().spawn_empty().id();
+    app.world_mut()
+        .spawn((
+            ProjectileComponent {
+                projectile_type: ProjectileType::Bullet(faction),
+                owner,
+                damage: 20,
+                damage_type: DamageType::Physical,
+                critical: CriticalStats::default(),
+                velocity,
+                radius: 16.0,
+                lifetime: Timer::from_seconds(10.0, TimerMode::Once),
+                hit: Default::default(),
+            },
+            Transform::from_translation(position.extend(0.0)),
+        ))
+        .id()
+}
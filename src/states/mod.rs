@@ -1,8 +1,10 @@
+use assets::audio::AudioAssets;
 use assets::player::shadow::PlayerShadowAssets;
 use assets::spawnable::projectile::ProjectileAssets;
+use assets::ui::UiAssets;
 use assets::weapon::WeaponAssets;
 use bevy::prelude::{
-    apply_deferred, App, AppExtStates, IntoSystemSetConfigs, NextState,
+    apply_deferred, App, AppExtStates, IntoSystemConfigs, NextState,
     OnEnter, OnExit, Plugin, Res, ResMut, State,
 };
 use bevy_asset_loader::prelude::*;
@@ -28,14 +30,22 @@ impl Plugin for StatesPlugin {
                 .with_dynamic_assets_file::<StandardDynamicAssetCollection>(
                     "projectile_assets.assets.ron",
                 )
+                .with_dynamic_assets_file::<StandardDynamicAssetCollection>(
+                    "ui_assets.assets.ron",
+                )
+                .with_dynamic_assets_file::<StandardDynamicAssetCollection>(
+                    "audio_assets.assets.ron",
+                )
                 .load_collection::<PlayerShadowAssets>()
                 .load_collection::<WeaponAssets>()
-                .load_collection::<ProjectileAssets>(),
+                .load_collection::<ProjectileAssets>()
+                .load_collection::<UiAssets>()
+                .load_collection::<AudioAssets>(),
         );
 
         app.add_systems(
             OnEnter(AppStates::GameInit),
-            (spawn_player_system, spawn_weapon_system),
+            (spawn_player_system, spawn_weapon_system).chain(),
         );
 
         app.add_systems(
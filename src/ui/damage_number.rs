@@ -0,0 +1,432 @@
+use std::collections::{HashMap, VecDeque};
+
+use assets::ui::{UiAssets, UiFont};
+use bevy::prelude::*;
+use engine::combat::{DamageAppliedEvent, HealAppliedEvent};
+use engine::player::PlayerComponent;
+use engine::settings::SettingsResource;
+use engine::states::util::GameCleanup;
+use serde::Deserialize;
+
+/// Draw order of damage numbers, above every sprite.
+const DAMAGE_NUMBER_Z: f32 = 10.0;
+
+/// What a damage number shows, which picks its style.
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DamageNumberKind {
+    Normal,
+    Critical,
+    Heal,
+    /// Damage taken by a player.
+    PlayerDamage,
+}
+
+/// Look of the damage numbers of one kind.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct DamageNumberStyle {
+    pub font: UiFont,
+    pub font_size: f32,
+    pub color: Srgba,
+    /// Text drawn before the number, e.g. `"+"` for heals.
+    #[serde(default)]
+    pub prefix: String,
+}
+
+/// Tuning of the floating damage numbers, loaded from `damage_numbers.ron`.
+#[derive(Resource, Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct DamageNumbersResource {
+    /// Most damage numbers shown at once. Once they are all shown, the
+    /// oldest one is reused.
+    pub pool_size: usize,
+    /// Seconds during which further hits of the same kind on a target add up
+    /// on its number instead of showing a new one.
+    pub merge_window: f32,
+    /// Seconds a number stays visible after its last hit.
+    pub lifetime: f32,
+    /// Units per second a number rises while it fades out.
+    pub rise_speed: f32,
+    /// Offset of a new number from the position of its target.
+    pub offset: Vec2,
+    pub normal: DamageNumberStyle,
+    pub critical: DamageNumberStyle,
+    pub heal: DamageNumberStyle,
+    pub player_damage: DamageNumberStyle,
+}
+
+impl DamageNumbersResource {
+    pub fn style(&self, kind: DamageNumberKind) -> &DamageNumberStyle {
+        match kind {
+            DamageNumberKind::Normal => &self.normal,
+            DamageNumberKind::Critical => &self.critical,
+            DamageNumberKind::Heal => &self.heal,
+            DamageNumberKind::PlayerDamage => &self.player_damage,
+        }
+    }
+}
+
+/// Floating number of damage or healing shown above a target.
+#[derive(Component, Debug)]
+pub struct DamageNumberComponent {
+    pub target: Entity,
+    pub kind: DamageNumberKind,
+    /// Damage or healing added up over the merge window.
+    pub value: u32,
+    /// Time left before the number stops taking further hits.
+    pub merge_timer: Timer,
+    /// Time left before the number is hidden.
+    pub lifetime: Timer,
+}
+
+impl DamageNumberComponent {
+    /// Returns `true` while the number is shown.
+    pub fn is_active(&self) -> bool {
+        !self.lifetime.finished()
+    }
+}
+
+/// Entities of the damage numbers, reused instead of spawning one text
+/// entity per hit.
+#[derive(Resource, Default, Debug)]
+pub struct DamageNumberPool {
+    /// Every pooled entity, least recently shown first.
+    numbers: VecDeque<Entity>,
+    /// Number still taking hits for every target and kind.
+    merging: HashMap<(Entity, DamageNumberKind), Entity>,
+}
+
+/// Shows the damage and healing of the frame as floating numbers.
+///
+/// Hits of one kind on one target within `merge_window` add up on a single
+/// number, so a target shows at most one number per kind and window however
+/// many hits it takes. Numbers are taken from the [`DamageNumberPool`], which
+/// spawns at most `pool_size` of them and then reuses the oldest one.
+#[allow(clippy::too_many_arguments)]
+pub fn spawn_damage_numbers_system(
+    mut commands: Commands,
+    mut damage_events: EventReader<DamageAppliedEvent>,
+    mut heal_events: EventReader<HealAppliedEvent>,
+    settings: Res<SettingsResource>,
+    config: Res<DamageNumbersResource>,
+    ui_assets: Res<UiAssets>,
+    mut pool: ResMut<DamageNumberPool>,
+    target_query: Query<
+        (&Transform, Has<PlayerComponent>),
+        Without<DamageNumberComponent>,
+    >,
+    mut number_query: Query<(
+        &mut DamageNumberComponent,
+        &mut Text2d,
+        &mut TextFont,
+        &mut TextColor,
+        &mut Transform,
+        &mut Visibility,
+    )>,
+) {
+    if !settings.damage_numbers {
+        damage_events.clear();
+        heal_events.clear();
+        return;
+    }
+
+    // Add up the hits of the frame first, so that a burst of hits on one
+    // target touches a single number
+    let mut hits: HashMap<(Entity, DamageNumberKind), u32> = HashMap::new();
+    for event in damage_events.read() {
+        let Ok((_, is_player)) = target_query.get(event.target) else {
+            continue;
+        };
+        let kind = if is_player {
+            DamageNumberKind::PlayerDamage
+        } else if event.is_critical {
+            DamageNumberKind::Critical
+        } else {
+            DamageNumberKind::Normal
+        };
+        *hits.entry((event.target, kind)).or_default() += event.damage;
+    }
+    for event in heal_events.read() {
+        *hits
+            .entry((event.target, DamageNumberKind::Heal))
+            .or_default() += event.amount;
+    }
+
+    for ((target, kind), value) in hits {
+        if value == 0 {
+            continue;
+        }
+        let Ok((target_transform, _)) = target_query.get(target) else {
+            continue;
+        };
+        let style = config.style(kind);
+        let lifetime = Timer::from_seconds(config.lifetime, TimerMode::Once);
+
+        // Add up on the number still merging hits, if any
+        if let Some(entity) = pool.merging.get(&(target, kind)).copied() {
+            if let Ok((mut number, mut text, ..)) = number_query.get_mut(entity)
+            {
+                if number.is_active() && !number.merge_timer.finished() {
+                    number.value += value;
+                    number.lifetime = lifetime;
+                    text.0 = format!("{}{}", style.prefix, number.value);
+                    continue;
+                }
+            }
+            pool.merging.remove(&(target, kind));
+        }
+
+        let translation = (target_transform.translation.truncate()
+            + config.offset)
+            .extend(DAMAGE_NUMBER_Z);
+        let number = DamageNumberComponent {
+            target,
+            kind,
+            value,
+            merge_timer: Timer::from_seconds(
+                config.merge_window,
+                TimerMode::Once,
+            ),
+            lifetime,
+        };
+        let text = format!("{}{}", style.prefix, value);
+        let font = TextFont {
+            font: ui_assets.get_font(style.font),
+            font_size: style.font_size,
+            ..default()
+        };
+        let color = TextColor(style.color.into());
+
+        // A full pool reuses its oldest number, unless that one is gone
+        let reusable = if pool.numbers.len() < config.pool_size {
+            None
+        } else {
+            pool.numbers.pop_front()
+        };
+        let pooled = reusable.and_then(|entity| {
+            number_query
+                .get_mut(entity)
+                .ok()
+                .map(|parts| (entity, parts))
+        });
+        let entity = match pooled {
+            Some((
+                entity,
+                (
+                    mut pooled,
+                    mut pooled_text,
+                    mut pooled_font,
+                    mut pooled_color,
+                    mut transform,
+                    mut visibility,
+                ),
+            )) => {
+                pool.merging.remove(&(pooled.target, pooled.kind));
+                *pooled = number;
+                pooled_text.0 = text;
+                *pooled_font = font;
+                *pooled_color = color;
+                transform.translation = translation;
+                *visibility = Visibility::Inherited;
+                entity
+            },
+            None => commands
+                .spawn((
+                    Name::new("Damage Number"),
+                    Text2d::new(text),
+                    font,
+                    color,
+                    Transform::from_translation(translation),
+                    number,
+                    GameCleanup,
+                ))
+                .id(),
+        };
+        pool.numbers.push_back(entity);
+        pool.merging.insert((target, kind), entity);
+    }
+}
+
+/// Despawns the damage numbers and empties the [`DamageNumberPool`] when a
+/// run ends, so that no number is left frozen on screen or carried over into
+/// the next run.
+pub fn clear_damage_numbers_system(
+    mut commands: Commands,
+    mut pool: ResMut<DamageNumberPool>,
+) {
+    for entity in pool.numbers.drain(..) {
+        if let Some(entity_commands) = commands.get_entity(entity) {
+            entity_commands.despawn_recursive();
+        }
+    }
+    pool.merging.clear();
+}
+
+/// Lifts and fades out the shown damage numbers, and hides them once their
+/// lifetime is over so that they can be reused.
+pub fn animate_damage_numbers_system(
+    time: Res<Time>,
+    config: Res<DamageNumbersResource>,
+    mut number_query: Query<(
+        &mut DamageNumberComponent,
+        &mut TextColor,
+        &mut Transform,
+        &mut Visibility,
+    )>,
+) {
+    for (mut number, mut color, mut transform, mut visibility) in
+        number_query.iter_mut()
+    {
+        if !number.is_active() {
+            continue;
+        }
+        number.merge_timer.tick(time.delta());
+        if number.lifetime.tick(time.delta()).finished() {
+            *visibility = Visibility::Hidden;
+            continue;
+        }
+
+        transform.translation.y += config.rise_speed * time.delta_secs();
+        let alpha = config.style(number.kind).color.alpha
+            * number.lifetime.fraction_remaining();
+        color.0.set_alpha(alpha);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use engine::combat::DamageType;
+
+    use super::*;
+
+    fn app() -> App {
+        let config: DamageNumbersResource = ron::de::from_bytes(
+            include_bytes!("../../assets/data/damage_numbers.ron"),
+        )
+        .unwrap();
+        let mut app = App::new();
+        app.init_resource::<Time>()
+            .init_resource::<SettingsResource>()
+            .init_resource::<DamageNumberPool>()
+            .insert_resource(DamageNumbersResource {
+                pool_size: 4,
+                ..config
+            })
+            .insert_resource(UiAssets {
+                regular_font: Handle::default(),
+                display_font: Handle::default(),
+                title_font: Handle::default(),
+            })
+            .add_event::<DamageAppliedEvent>()
+            .add_event::<HealAppliedEvent>()
+            .add_systems(
+                Update,
+                (
+                    spawn_damage_numbers_system,
+                    animate_damage_numbers_system,
+                )
+                    .chain(),
+            );
+        app
+    }
+
+    fn hit(app: &mut App, target: Entity, damage: u32, is_critical: bool) {
+        app.world_mut().send_event(DamageAppliedEvent {
+            target,
+            damage,
+            damage_type: DamageType::Physical,
+            is_critical,
+            source: None,
+        });
+    }
+
+    fn numbers(app: &mut App) -> Vec<(DamageNumberKind, u32)> {
+        let mut numbers: Vec<_> = app
+            .world_mut()
+            .query::<&DamageNumberComponent>()
+            .iter(app.world())
+            .map(|number| (number.kind, number.value))
+            .collect();
+        numbers.sort_by_key(|(_, value)| *value);
+        numbers
+    }
+
+    #[test]
+    fn hits_on_a_target_add_up_on_one_number() {
+        let mut app = app();
+        let enemy = app.world_mut().spawn(Transform::default()).id();
+
+        for _ in 0..1000 {
+            hit(&mut app, enemy, 1, false);
+        }
+        hit(&mut app, enemy, 50, true);
+        app.update();
+        hit(&mut app, enemy, 5, false);
+        app.update();
+
+        assert_eq!(
+            numbers(&mut app),
+            vec![
+                (DamageNumberKind::Critical, 50),
+                (DamageNumberKind::Normal, 1005),
+            ]
+        );
+    }
+
+    #[test]
+    fn pool_reuses_numbers_once_full() {
+        let mut app = app();
+
+        for _ in 0..10 {
+            let enemy = app.world_mut().spawn(Transform::default()).id();
+            hit(&mut app, enemy, 1, false);
+            app.update();
+        }
+
+        assert_eq!(numbers(&mut app).len(), 4);
+    }
+
+    #[test]
+    fn despawned_pooled_number_is_replaced_by_a_new_one() {
+        let mut app = app();
+        for _ in 0..4 {
+            let enemy = app.world_mut().spawn(Transform::default()).id();
+            hit(&mut app, enemy, 1, false);
+            app.update();
+        }
+        let oldest = app.world().resource::<DamageNumberPool>().numbers[0];
+        app.world_mut().despawn(oldest);
+
+        let enemy = app.world_mut().spawn(Transform::default()).id();
+        hit(&mut app, enemy, 2, false);
+        app.update();
+
+        assert_eq!(
+            numbers(&mut app),
+            vec![
+                (DamageNumberKind::Normal, 1),
+                (DamageNumberKind::Normal, 1),
+                (DamageNumberKind::Normal, 1),
+                (DamageNumberKind::Normal, 2),
+            ]
+        );
+        assert_eq!(
+            app.world().resource::<DamageNumberPool>().numbers.len(),
+            4
+        );
+    }
+
+    #[test]
+    fn clearing_empties_the_pool() {
+        let mut app = app();
+        app.add_systems(PostUpdate, clear_damage_numbers_system);
+        let enemy = app.world_mut().spawn(Transform::default()).id();
+
+        hit(&mut app, enemy, 1, false);
+        app.update();
+        assert!(numbers(&mut app).is_empty());
+
+        let pool = app.world().resource::<DamageNumberPool>();
+        assert!(pool.numbers.is_empty() && pool.merging.is_empty());
+    }
+}
@@ -1,9 +1,30 @@
 use bevy::prelude::*;
+use engine::states::app::AppStates;
+
+use crate::ui::damage_number::{
+    animate_damage_numbers_system, clear_damage_numbers_system,
+    spawn_damage_numbers_system, DamageNumberPool,
+};
+
+pub mod damage_number;
 
 pub struct UiPlugin;
 
 impl Plugin for UiPlugin {
     fn build(&self, app: &mut App) {
-        todo!("Ui Plugin")
+        app.init_resource::<DamageNumberPool>()
+            .add_systems(
+                Update,
+                (
+                    spawn_damage_numbers_system,
+                    animate_damage_numbers_system,
+                )
+                    .chain()
+                    .run_if(in_state(AppStates::InGame)),
+            )
+            .add_systems(
+                OnExit(AppStates::InGame),
+                clear_damage_numbers_system,
+            );
     }
 }
@@ -1,59 +1,138 @@
 mod systems;
 
-use bevy::app::App;
-use bevy::math::Vec3;
-use bevy::prelude::{in_state, Commands, IntoSystemConfigs, Name, OnEnter, Plugin, Res, ResMut, Transform, Update};
-use bevy::sprite::Sprite;
-use assets::player::shadow::PlayerShadowAssets;
+use bevy::prelude::*;
 use assets::weapon::WeaponAssets;
-use engine::abilities::AbilitiesResource;
-use engine::input::InputsResource;
-use engine::player::PlayersResource;
+use engine::events::reward::{RewardClaimedEvent, WeaponEvolvedEvent};
+use engine::player::{PlayerComponent, PlayersResource};
 use engine::states::app::AppStates;
-use engine::weapon::WeaponComponent;
-use crate::animation::animation::AnimationsResource;
-use crate::game::resources::GameResource;
+use engine::weapon::{
+    SwapWeaponEvent, WeaponComponent, WeaponId, WeaponsResource,
+};
 use crate::player::character::CharactersResource;
+use crate::weapon::systems::auto_fire::auto_fire_weapons_system;
+use crate::weapon::systems::evolution::claim_reward_system;
 use crate::weapon::systems::transform::update_weapon_transform;
 
 pub struct WeaponPlugin;
 
 impl Plugin for WeaponPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
-            Update,
-            (update_weapon_transform).run_if(in_state(AppStates::InGame))
-        );
+        app.add_event::<SwapWeaponEvent>()
+            .add_event::<RewardClaimedEvent>()
+            .add_event::<WeaponEvolvedEvent>()
+            .add_systems(
+                Update,
+                (
+                    claim_reward_system,
+                    swap_weapon_system,
+                    weapon_cooldown_system,
+                    update_weapon_transform,
+                )
+                    .chain()
+                    .run_if(in_state(AppStates::InGame))
+            )
+            .add_systems(
+                Update,
+                auto_fire_weapons_system.run_if(in_state(AppStates::InGame)),
+            );
     }
 }
 
+/// Spawns the weapon every player holds at the start of a run, as defined by
+/// the `weapon` of its character. The weapon is a child of the player, so it
+/// follows it and is despawned with it.
 pub fn spawn_weapon_system(
     mut commands: Commands,
+    players_res: Res<PlayersResource>,
     characters_res: Res<CharactersResource>,
-    game_parameters: Res<GameResource>,
+    weapons_res: Res<WeaponsResource>,
     weapon_assets: Res<WeaponAssets>,
+    player_query: Query<Entity, With<PlayerComponent>>,
+) {
+    let Some(character) = players_res
+        .player_data
+        .as_ref()
+        .and_then(|data| characters_res.characters.get(&data.character))
+    else {
+        error!("Player character not found, no weapon spawned");
+        return;
+    };
+
+    for owner in player_query.iter() {
+        spawn_weapon(
+            &mut commands,
+            &weapons_res,
+            &weapon_assets,
+            owner,
+            &character.weapon,
+        );
+    }
+}
+
+fn spawn_weapon(
+    commands: &mut Commands,
+    weapons_res: &WeaponsResource,
+    weapon_assets: &WeaponAssets,
+    owner: Entity,
+    id: &WeaponId,
 ) {
-    let mut weapon_entity_commands = commands.spawn_empty();
-    weapon_entity_commands.insert(WeaponComponent);
-    weapon_entity_commands.insert(
-        (
-            Name::new("Ice Staff"),
-            Transform {
-                translation: Vec3::ZERO,
-                scale: Vec3::new(
-                    0.8,
-                    0.8,
-                    1.0,
-                ),
-                ..Default::default()
-            },
-            Sprite {
-                image: weapon_assets.staff_image.clone(),
-                ..Default::default()
-            }
-        )
-    );
-    log::info!(
-        "Weapon spawned!"
-    );
-}
\ No newline at end of file
+    let Some(data) = weapons_res.get(id) else {
+        error!("Weapon `{}` not found", id);
+        return;
+    };
+
+    commands.entity(owner).with_child((
+        Name::new(data.name.clone()),
+        Transform::from_scale(Vec3::new(data.scale, data.scale, 1.0)),
+        Sprite {
+            image: weapon_assets.get_image(&data.sprite),
+            ..Default::default()
+        },
+        WeaponComponent::new(owner, id.clone(), data.clone()),
+    ));
+    info!("Weapon {} spawned for {:?}", data.name, owner);
+}
+
+/// Swaps the weapon held by a player for the one requested through
+/// [`SwapWeaponEvent`], updating its name and sprite.
+fn swap_weapon_system(
+    mut swap_events: EventReader<SwapWeaponEvent>,
+    weapons_res: Res<WeaponsResource>,
+    weapon_assets: Res<WeaponAssets>,
+    mut weapon_query: Query<(
+        &mut WeaponComponent,
+        &mut Name,
+        &mut Sprite,
+        &mut Transform,
+    )>,
+) {
+    for event in swap_events.read() {
+        let Some(data) = weapons_res.get(&event.weapon) else {
+            error!("Weapon `{}` not found", event.weapon);
+            continue;
+        };
+        let Some((mut weapon, mut name, mut sprite, mut transform)) =
+            weapon_query
+                .iter_mut()
+                .find(|(weapon, ..)| weapon.owner == event.entity)
+        else {
+            warn!("{:?} holds no weapon to swap", event.entity);
+            continue;
+        };
+
+        weapon.swap(event.weapon.clone(), data.clone());
+        *name = Name::new(data.name.clone());
+        sprite.image = weapon_assets.get_image(&data.sprite);
+        transform.scale = Vec3::new(data.scale, data.scale, 1.0);
+    }
+}
+
+/// Advances the cooldown of every held weapon.
+fn weapon_cooldown_system(
+    time: Res<Time>,
+    mut weapon_query: Query<&mut WeaponComponent>,
+) {
+    for mut weapon in weapon_query.iter_mut() {
+        weapon.cooldown.tick(time.delta());
+    }
+}
@@ -0,0 +1,379 @@
+use std::f32::consts::TAU;
+
+use assets::spawnable::projectile::ProjectileAssets;
+use bevy::prelude::*;
+use engine::aim::AimComponent;
+use engine::combat::CombatRngResource;
+use engine::health::HealthComponent;
+use engine::player::{PlayerComponent, PlayerOutgoingDamageComponent};
+use engine::spawnable::mob::EnemyComponent;
+use engine::spawnable::projectile::behavior::{
+    OrbitComponent, ProjectilesResource,
+};
+use engine::spawnable::projectile::{
+    spread_directions, ProjectileComponent, ProjectileType,
+    PROJECTILE_HIT_RADIUS,
+};
+use engine::spawnable::Faction;
+use engine::status_effect::StatusEffectsComponent;
+use engine::weapon::{WeaponData, WeaponInventoryComponent, WeaponTargeting};
+use rand::Rng;
+
+use crate::game::resources::GameResource;
+use crate::spawnable::projectile::systems::spawn::{
+    player_projectile_budget, spawn_projectile,
+};
+
+/// Fires every weapon of a player's inventory once its cooldown is over.
+///
+/// The direction of a shot depends on the weapon's [`WeaponTargeting`].
+/// `Nearest` weapons hold their fire until an enemy is in range, and fire as
+/// soon as one is. Orbiting projectiles are spread evenly around the player.
+///
+/// Shots scale with the player's [`PlayerOutgoingDamageComponent`]: damage,
+/// speed, size, lifetime and critical stats come from it, and its projectile
+/// count adds to the weapon's. Shots are trimmed so that at most
+/// `max_player_projectiles` player projectiles are alive at once; a weapon
+/// holds its fire while none can be spawned.
+///
+/// Weapons hold their fire, and their cooldowns pause, while the player is
+/// stunned.
+#[allow(clippy::too_many_arguments)]
+pub fn auto_fire_weapons_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut rng: ResMut<CombatRngResource>,
+    game_parameters: Res<GameResource>,
+    projectile_assets: Res<ProjectileAssets>,
+    projectiles_res: Res<ProjectilesResource>,
+    mut player_query: Query<
+        (
+            Entity,
+            &Transform,
+            &AimComponent,
+            &PlayerOutgoingDamageComponent,
+            &mut WeaponInventoryComponent,
+            Option<&StatusEffectsComponent>,
+        ),
+        With<PlayerComponent>,
+    >,
+    enemy_query: Query<(&Transform, &HealthComponent), With<EnemyComponent>>,
+    projectile_query: Query<&ProjectileComponent>,
+) {
+    let mut budget =
+        player_projectile_budget(&game_parameters, &projectile_query);
+
+    for (
+        entity,
+        transform,
+        aim,
+        outgoing_damage,
+        mut inventory,
+        status_effects,
+    ) in player_query.iter_mut()
+    {
+        if status_effects.is_some_and(StatusEffectsComponent::is_stunned) {
+            continue;
+        }
+        let position = transform.translation.truncate();
+
+        for weapon in inventory.weapons.iter_mut() {
+            if !weapon.cooldown.tick(time.delta()).finished() || budget == 0 {
+                continue;
+            }
+
+            let direction = match weapon.data.targeting {
+                WeaponTargeting::Nearest => {
+                    let Some(target) = nearest_enemy(
+                        &enemy_query,
+                        position,
+                        weapon.data.range,
+                    ) else {
+                        continue;
+                    };
+                    (target - position).normalize_or(Vec2::X)
+                },
+                WeaponTargeting::Facing => aim.direction,
+                WeaponTargeting::Random => {
+                    Vec2::from_angle(rng.0.gen_range(0.0..TAU))
+                },
+                WeaponTargeting::Orbit { .. } => Vec2::X,
+            };
+            weapon.cooldown.reset();
+
+            let Some(projectile_data) =
+                projectiles_res.get(&weapon.data.projectile)
+            else {
+                error!(
+                    "Projectile kind `{}` not found",
+                    weapon.data.projectile
+                );
+                continue;
+            };
+
+            let count = (weapon.data.projectile_count
+                + outgoing_damage.projectile_count.saturating_sub(1))
+            .min(budget);
+            budget -= count;
+            let directions = match weapon.data.targeting {
+                WeaponTargeting::Orbit { .. } => (0..count)
+                    .map(|i| Vec2::from_angle(TAU * i as f32 / count as f32))
+                    .collect(),
+                _ => spread_directions(
+                    direction,
+                    count,
+                    game_parameters.projectile_gap,
+                    game_parameters.max_spread_arc,
+                ),
+            };
+
+            for direction in directions {
+                let projectile =
+                    weapon_projectile(entity, &weapon.data, outgoing_damage);
+                match weapon.data.targeting {
+                    WeaponTargeting::Orbit {
+                        radius,
+                        angular_speed,
+                    } => {
+                        let translation = (position + direction * radius)
+                            .extend(transform.translation.z + 0.1);
+                        let orbiting = spawn_projectile(
+                            &mut commands,
+                            &projectile_assets,
+                            ProjectileComponent {
+                                velocity: Vec2::ZERO,
+                                ..projectile
+                            },
+                            projectile_data,
+                            translation,
+                            outgoing_damage.projectile_size,
+                        );
+                        commands.entity(orbiting).insert(OrbitComponent {
+                            center: entity,
+                            radius,
+                            angular_speed,
+                            angle: direction.to_angle(),
+                        });
+                    },
+                    _ => {
+                        spawn_projectile(
+                            &mut commands,
+                            &projectile_assets,
+                            ProjectileComponent {
+                                velocity: direction
+                                    * outgoing_damage.projectile_speed
+                                    * weapon.data.speed_multiplier,
+                                ..projectile
+                            },
+                            projectile_data,
+                            position.extend(transform.translation.z + 0.1),
+                            outgoing_damage.projectile_size,
+                        );
+                    },
+                }
+            }
+        }
+    }
+}
+
+/// Position of the nearest living enemy within `range` of `position`.
+fn nearest_enemy(
+    enemy_query: &Query<(&Transform, &HealthComponent), With<EnemyComponent>>,
+    position: Vec2,
+    range: f32,
+) -> Option<Vec2> {
+    enemy_query
+        .iter()
+        .filter(|(_, health)| !health.is_dead())
+        .map(|(enemy_transform, _)| enemy_transform.translation.truncate())
+        .filter(|enemy| enemy.distance(position) <= range)
+        .min_by(|a, b| {
+            a.distance_squared(position)
+                .total_cmp(&b.distance_squared(position))
+        })
+}
+
+/// Projectile of a shot of `weapon` fired by `owner`, without velocity.
+fn weapon_projectile(
+    owner: Entity,
+    weapon: &WeaponData,
+    outgoing_damage: &PlayerOutgoingDamageComponent,
+) -> ProjectileComponent {
+    ProjectileComponent {
+        projectile_type: ProjectileType::Bullet(Faction::Ally),
+        owner,
+        damage: weapon.hit_damage(outgoing_damage.weapon_damage),
+        damage_type: weapon.damage_type,
+        critical: outgoing_damage.critical,
+        velocity: Vec2::ZERO,
+        radius: PROJECTILE_HIT_RADIUS * outgoing_damage.projectile_size,
+        lifetime: Timer::from_seconds(
+            outgoing_damage.projectile_despawn_time,
+            TimerMode::Once,
+        ),
+        hit: Default::default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use engine::combat::{CriticalStats, DamageType};
+    use engine::spawnable::projectile::behavior::ProjectileId;
+    use engine::spawnable::SpawnPosition;
+    use engine::weapon::{WeaponId, WeaponSprite};
+
+    use super::*;
+    use crate::game::data::GameData;
+
+    fn app() -> App {
+        let mut app = App::new();
+        let data = GameData::load(None).unwrap();
+        app.init_resource::<Time>()
+            .insert_resource(CombatRngResource::seeded(0))
+            .insert_resource(data.game_parameters)
+            .insert_resource(data.projectiles)
+            .insert_resource(ProjectileAssets {
+                ally_bullet_layout: Handle::default(),
+                ally_bullet_image: Handle::default(),
+                enemy_bullet_layout: Handle::default(),
+                enemy_bullet_image: Handle::default(),
+            })
+            .add_systems(Update, auto_fire_weapons_system);
+        app
+    }
+
+    fn spawn_player(app: &mut App, targeting: WeaponTargeting) -> Entity {
+        let mut inventory = WeaponInventoryComponent::default();
+        inventory.equip(
+            WeaponId::new("Test"),
+            WeaponData {
+                name: "Test".to_string(),
+                sprite: WeaponSprite::IceStaff,
+                scale: 1.0,
+                pivot: Vec2::ZERO,
+                hold_radius: 0.0,
+                turn_rate: 1.0,
+                damage: 3,
+                damage_multiplier: 1.2,
+                damage_type: DamageType::Physical,
+                fire_rate: 1.0,
+                projectile: ProjectileId::new("Bullet"),
+                targeting,
+                speed_multiplier: 1.0,
+                projectile_count: 1,
+                range: 200.0,
+                max_level: 1,
+            },
+            1,
+        );
+        app.world_mut()
+            .spawn((
+                PlayerComponent,
+                Transform::default(),
+                AimComponent::default(),
+                PlayerOutgoingDamageComponent {
+                    collision_damage: 0,
+                    weapon_damage: 10,
+                    projectile_speed: 100.0,
+                    projectile_despawn_time: 1.0,
+                    projectile_size: 1.0,
+                    projectile_count: 2,
+                    projectile: ProjectileId::new("Bullet"),
+                    projectile_spawn_position: SpawnPosition::Local(Vec2::ZERO),
+                    critical: CriticalStats::default(),
+                },
+                inventory,
+            ))
+            .id()
+    }
+
+    fn advance(app: &mut App, seconds: f32) {
+        app.world_mut()
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_secs_f32(seconds));
+        app.update();
+    }
+
+    fn projectiles(app: &mut App) -> Vec<u32> {
+        app.world_mut()
+            .query::<&ProjectileComponent>()
+            .iter(app.world())
+            .map(|projectile| projectile.damage)
+            .collect()
+    }
+
+    #[test]
+    fn fires_on_cooldown_with_scaled_stats() {
+        let mut app = app();
+        spawn_player(&mut app, WeaponTargeting::Facing);
+
+        advance(&mut app, 0.5);
+        assert!(projectiles(&mut app).is_empty());
+
+        advance(&mut app, 0.5);
+        assert_eq!(projectiles(&mut app), vec![15, 15]);
+    }
+
+    #[test]
+    fn facing_targeting_fires_along_aim() {
+        let mut app = app();
+        let player = spawn_player(&mut app, WeaponTargeting::Facing);
+        app.world_mut()
+            .get_mut::<AimComponent>(player)
+            .unwrap()
+            .set(Vec2::NEG_Y);
+
+        advance(&mut app, 1.0);
+
+        let direction: Vec2 = app
+            .world_mut()
+            .query::<&ProjectileComponent>()
+            .iter(app.world())
+            .map(|projectile| projectile.velocity)
+            .sum();
+        assert!((direction.normalize() - Vec2::NEG_Y).length() < 1e-4);
+    }
+
+    #[test]
+    fn shots_are_trimmed_to_max_player_projectiles() {
+        let mut app = app();
+        app.world_mut()
+            .resource_mut::<GameResource>()
+            .max_player_projectiles = 3;
+        spawn_player(&mut app, WeaponTargeting::Facing);
+
+        advance(&mut app, 1.0);
+        assert_eq!(projectiles(&mut app).len(), 2);
+
+        advance(&mut app, 1.0);
+        assert_eq!(projectiles(&mut app).len(), 3);
+
+        advance(&mut app, 1.0);
+        assert_eq!(projectiles(&mut app).len(), 3);
+    }
+
+    #[test]
+    fn nearest_targeting_holds_fire_until_enemy_in_range() {
+        let mut app = app();
+        spawn_player(&mut app, WeaponTargeting::Nearest);
+        let enemy = app
+            .world_mut()
+            .spawn((
+                EnemyComponent,
+                HealthComponent::new(10),
+                Transform::from_xyz(500.0, 0.0, 0.0),
+            ))
+            .id();
+
+        advance(&mut app, 1.0);
+        assert!(projectiles(&mut app).is_empty());
+
+        app.world_mut()
+            .entity_mut(enemy)
+            .insert(Transform::from_xyz(100.0, 0.0, 0.0));
+        advance(&mut app, 0.0);
+        assert_eq!(projectiles(&mut app).len(), 2);
+    }
+}
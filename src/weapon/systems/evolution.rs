@@ -0,0 +1,183 @@
+use bevy::prelude::*;
+use engine::events::reward::{Reward, RewardClaimedEvent, WeaponEvolvedEvent};
+use engine::passive::{PassiveInventoryComponent, PassivesResource};
+use engine::weapon::{
+    EvolutionsResource, SwapWeaponEvent, WeaponComponent,
+    WeaponInventoryComponent, WeaponsResource,
+};
+
+use crate::game::resources::GameResource;
+
+/// Applies every claimed chest or level-up reward, then evolves the player's
+/// weapons whose recipe is ready.
+///
+/// A weapon reward is added to the inventory, or raises the weapon's level
+/// if it is already held. A passive reward does the same for passive items.
+/// An evolved weapon keeps its inventory slot, and the weapon the player
+/// holds is swapped for its evolution too.
+///
+/// There are no chests or level-ups yet, so nothing sends a
+/// [`RewardClaimedEvent`] outside of tests.
+#[allow(clippy::too_many_arguments)]
+pub fn claim_reward_system(
+    mut reward_events: EventReader<RewardClaimedEvent>,
+    mut swap_events: EventWriter<SwapWeaponEvent>,
+    mut evolved_events: EventWriter<WeaponEvolvedEvent>,
+    game_parameters: Res<GameResource>,
+    weapons_res: Res<WeaponsResource>,
+    passives_res: Res<PassivesResource>,
+    evolutions_res: Res<EvolutionsResource>,
+    mut inventory_query: Query<(
+        &mut WeaponInventoryComponent,
+        &mut PassiveInventoryComponent,
+    )>,
+    held_weapon_query: Query<&WeaponComponent>,
+) {
+    for event in reward_events.read() {
+        let Ok((mut weapons, mut passives)) =
+            inventory_query.get_mut(event.entity)
+        else {
+            continue;
+        };
+
+        match &event.reward {
+            Reward::Weapon(id) => {
+                if weapons.contains(id) {
+                    weapons.level_up(id);
+                } else if let Some(data) = weapons_res.get(id) {
+                    weapons.equip(
+                        id.clone(),
+                        data.clone(),
+                        game_parameters.max_weapons,
+                    );
+                } else {
+                    error!("Weapon `{}` not found", id);
+                }
+            },
+            Reward::Passive(id) => {
+                let Some(data) = passives_res.get(id) else {
+                    error!("Passive `{}` not found", id);
+                    continue;
+                };
+                passives.add(id.clone(), data.max_level);
+            },
+        }
+
+        for recipe in &evolutions_res.evolutions {
+            let Some(passive) = passives_res.get(&recipe.passive) else {
+                continue;
+            };
+            if !recipe.is_ready(&weapons, &passives, passive.max_level) {
+                continue;
+            }
+            let Some(evolved) = weapons_res.get(&recipe.evolved) else {
+                error!("Weapon `{}` not found", recipe.evolved);
+                continue;
+            };
+
+            weapons.evolve(
+                &recipe.weapon,
+                recipe.evolved.clone(),
+                evolved.clone(),
+            );
+            if held_weapon_query.iter().any(|held| {
+                held.owner == event.entity && held.id == recipe.weapon
+            }) {
+                swap_events.send(SwapWeaponEvent {
+                    entity: event.entity,
+                    weapon: recipe.evolved.clone(),
+                });
+            }
+            evolved_events.send(WeaponEvolvedEvent {
+                entity: event.entity,
+                weapon: recipe.weapon.clone(),
+                evolved: recipe.evolved.clone(),
+            });
+            info!(
+                "Weapon {} evolved into {} ({:?})",
+                recipe.weapon, recipe.evolved, event.source
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use engine::events::reward::RewardSource;
+    use engine::passive::PassiveId;
+    use engine::weapon::WeaponId;
+
+    use super::*;
+    use crate::game::data::GameData;
+
+    fn app() -> App {
+        let data = GameData::load(None).unwrap();
+        let mut app = App::new();
+        app.add_event::<RewardClaimedEvent>()
+            .add_event::<SwapWeaponEvent>()
+            .add_event::<WeaponEvolvedEvent>()
+            .insert_resource(data.game_parameters)
+            .insert_resource(data.weapons)
+            .insert_resource(data.passives)
+            .insert_resource(data.evolutions)
+            .add_systems(Update, claim_reward_system);
+        app
+    }
+
+    fn claim(app: &mut App, entity: Entity, reward: Reward, times: u32) {
+        for _ in 0..times {
+            app.world_mut().send_event(RewardClaimedEvent {
+                entity,
+                source: RewardSource::LevelUp,
+                reward: reward.clone(),
+            });
+        }
+        app.update();
+    }
+
+    #[test]
+    fn maxed_weapon_and_passive_evolve() {
+        let mut app = app();
+        let player = app
+            .world_mut()
+            .spawn((
+                WeaponInventoryComponent::default(),
+                PassiveInventoryComponent::default(),
+            ))
+            .id();
+        let wand = WeaponId::new("MagicWand");
+        let tome = PassiveId::new("ShadowTome");
+
+        claim(
+            &mut app,
+            player,
+            Reward::Weapon(wand.clone()),
+            8,
+        );
+        claim(
+            &mut app,
+            player,
+            Reward::Passive(tome.clone()),
+            4,
+        );
+        let weapons = app.world().get::<WeaponInventoryComponent>(player);
+        assert!(weapons.unwrap().contains(&wand));
+
+        claim(
+            &mut app,
+            player,
+            Reward::Passive(tome),
+            1,
+        );
+        let weapons = app.world().get::<WeaponInventoryComponent>(player);
+        let weapons = weapons.unwrap();
+        assert!(!weapons.contains(&wand));
+        assert!(weapons.contains(&WeaponId::new("SovereignWand")));
+
+        let events = app.world().resource::<Events<WeaponEvolvedEvent>>();
+        assert_eq!(
+            events.get_cursor().read(events).count(),
+            1
+        );
+    }
+}
@@ -1 +1,3 @@
+pub mod auto_fire;
+pub mod evolution;
 pub mod transform;
\ No newline at end of file
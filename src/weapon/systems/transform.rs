@@ -1,62 +1,44 @@
-use std::panic;
-
 use bevy::prelude::*;
-use bevy::window::PrimaryWindow;
-use engine::player::{
-    PlayerComponent, PlayerMobilityComponent, PlayerVelocityComponent,
-};
+use engine::aim::AimComponent;
+use engine::player::PlayerComponent;
 use engine::weapon::WeaponComponent;
-use leafwing_input_manager::prelude::MouseMove;
 
+/// Draw order of a held weapon, above its owner.
+const WEAPON_Z: f32 = 0.1;
+
+/// Turns every held weapon towards its owner's [`AimComponent`] and keeps it
+/// `hold_radius` away from its `pivot` along the direction it points to.
+///
+/// Weapons are children of their owner, so their transform is local to it:
+/// the radius and pivot grow with the owner's sprite scale, and the owner's
+/// horizontal flip mirrors them. The weapon's sprite is flipped while it
+/// points to the left so that it is never drawn upside down.
 pub fn update_weapon_transform(
-    windows_query: Query<&Window, With<PrimaryWindow>>,
+    time: Res<Time>,
     mut weapon_query: Query<
-        &mut Transform,
         (
-            With<WeaponComponent>,
-            Without<PlayerComponent>,
+            &mut Transform,
+            &mut Sprite,
+            &mut WeaponComponent,
         ),
+        Without<PlayerComponent>,
     >,
-    player_query: Query<&Transform, With<PlayerComponent>>,
-    camera_query: Query<(&Camera, &GlobalTransform), With<Camera2d>>,
+    player_query: Query<(&Transform, &AimComponent), With<PlayerComponent>>,
 ) {
-    if player_query.is_empty() || weapon_query.is_empty() {
-        warn!("Weapon or player is empty");
-        return;
-    }
-
-    let window = match windows_query.get_single() {
-        Ok(w) => w,
-        Err(_) => return,
-    };
-
-    let (camera, camera_transform) = match camera_query.get_single() {
-        Ok(c) => c,
-        Err(_) => return,
-    };
-
-    if let Some(cursor_screen_pos) = window.cursor_position() {
-        // TODO: burda karakteri ortalayacak sekilde x ve y ekseninde ayni oranda
-        // mesafe birakmaliyiz
-        if let Ok(ray) =
-            camera.viewport_to_world(camera_transform, cursor_screen_pos)
-        {
-            let cursor_pos = ray.origin;
-            let player_transform = player_query.single();
-            let mut weapon_transform = weapon_query.single_mut();
-
-            let player_pos = player_transform.translation;
-            let direction = (cursor_pos - player_pos).normalize_or_zero();
-            let distance_from_player = 300.0;
+    for (mut transform, mut sprite, mut weapon) in weapon_query.iter_mut() {
+        let Ok((player_transform, aim)) = player_query.get(weapon.owner) else {
+            continue;
+        };
+        weapon.turn_towards(aim.direction, time.delta_secs());
 
-            let weapon_pos = player_pos + direction * distance_from_player;
-            weapon_transform.translation = Vec3::new(
-                weapon_pos.x,
-                weapon_pos.y,
-                player_pos.z + 0.1,
-            );
-            weapon_transform.rotation =
-                Quat::from_rotation_z(direction.y.atan2(direction.x));
-        }
+        // Undo the owner's flip so the weapon points the same way in world
+        // space whichever way the owner faces
+        let aim =
+            weapon.aim * Vec2::new(player_transform.scale.x.signum(), 1.0);
+        transform.translation = (weapon.data.pivot
+            + aim * weapon.data.hold_radius)
+            .extend(WEAPON_Z);
+        transform.rotation = Quat::from_rotation_z(aim.to_angle());
+        sprite.flip_y = aim.x < 0.0;
     }
 }